@@ -0,0 +1,50 @@
+//! This module holds the checks that run over a parsed AST, which need to look
+//! at more than one node at a time (and so can't be done while parsing).
+
+pub mod captures;
+pub mod class_hierarchy;
+pub mod constants;
+pub mod constructors;
+pub mod diagnostic;
+pub mod exports;
+pub mod operators;
+pub mod type_aliases;
+pub mod walk;
+
+use super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+
+use captures::find_captures;
+use class_hierarchy::ClassHierarchy;
+use constants::check_constants;
+use constructors::check_constructors;
+use diagnostic::Diagnostic;
+use exports::check_exports;
+use operators::OperatorOverloads;
+use type_aliases::TypeAliases;
+
+/// Runs every check over the top-level nodes of a file, returning everything
+/// that's wrong with them. (An empty list means that the file is fine.)
+pub fn analyze(nodes: &[TopLevelAstNode]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    match TypeAliases::collect(nodes) {
+        Ok(type_aliases) => {
+            diagnostics.append(&mut type_aliases.check_declarations());
+            diagnostics.append(&mut type_aliases.check_uses(nodes));
+        }
+        Err(mut errors) => diagnostics.append(&mut errors),
+    }
+
+    diagnostics.append(&mut check_exports(nodes));
+    diagnostics.append(&mut check_constants(nodes));
+    diagnostics.append(&mut check_constructors(nodes));
+    diagnostics.append(&mut ClassHierarchy::collect(nodes).check(nodes));
+
+    let (operator_overloads, mut errors) = OperatorOverloads::collect(nodes);
+    diagnostics.append(&mut errors);
+    diagnostics.append(&mut operator_overloads.resolve(nodes).1);
+
+    diagnostics.append(&mut find_captures(nodes).1);
+
+    diagnostics
+}
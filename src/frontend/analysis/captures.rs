@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+
+use logos::Span;
+
+use super::super::parser::proper_parser::ast::documentation::Documented;
+use super::super::parser::proper_parser::ast::expressions::{
+    AstClosureBody, AstExpression, AstStatement, ClosureAstNode, ExpressionBlockAstNode,
+};
+use super::super::parser::proper_parser::ast::methods::{
+    AstMethodArgument, MethodList, MethodOrConstraintAstNode,
+};
+use super::super::parser::proper_parser::ast::patterns::{AstBindingPattern, AstPattern};
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::diagnostic::Diagnostic;
+
+/// This enum represents how a closure holds onto a variable from outside of it,
+/// the same way that `this` and `mut this` say how a method holds onto the value
+/// it is called on.
+///
+/// The kinds are ordered from weakest to strongest, so if a variable is used in
+/// more than one way, the strongest kind is the one that's needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CaptureKind {
+    ByReference,
+    ByMutableReference,
+    ByMove,
+}
+
+/// This struct represents a single variable that a closure captures. `span` is
+/// where the closure first uses it.
+#[derive(Debug)]
+pub struct Capture<'a> {
+    pub name: &'a str,
+    pub span: Span,
+    pub kind: CaptureKind,
+}
+
+/// This struct holds every variable that a single closure captures, in the
+/// order that they're first used.
+#[derive(Debug)]
+pub struct ClosureCaptures<'a> {
+    pub span: Span,
+    pub captures: Vec<Capture<'a>>,
+}
+
+/// Finds the captures of every closure in a file, reporting any closure that
+/// needs to mutate a variable (or `this`) that isn't mutable.
+///
+/// A variable is captured by mutable reference if the closure assigns to it (or
+/// to one of its fields or elements), and by move if the closure stores it or
+/// gives it back as a value (as the closure's result, with `return` or `yield`,
+/// or as the value of a `let` or an assignment). Otherwise, it is captured by
+/// reference. A closure inside of another closure makes the outer one capture
+/// the same variables.
+pub fn find_captures<'a>(
+    nodes: &[TopLevelAstNode<'a>],
+) -> (Vec<ClosureCaptures<'a>>, Vec<Diagnostic>) {
+    let mut finder = CaptureFinder {
+        scopes: Vec::new(),
+        closures: Vec::new(),
+        found: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+
+    for node in nodes {
+        match node.get_declaration() {
+            TopLevelAstNode::StructDec(_, struct_dec) => {
+                finder.find_in_methods(&struct_dec.methods)
+            }
+            TopLevelAstNode::ClassDec(_, class_dec) => {
+                for constructor in &class_dec.constructors {
+                    // Constructors always act on a `mut this`.
+                    let mut arg_scope = get_arg_scope(&constructor.node.args);
+                    arg_scope.insert(
+                        "this",
+                        Binding {
+                            span: constructor.node.span.clone(),
+                            is_mutable: true,
+                        },
+                    );
+
+                    finder.scopes = vec![arg_scope];
+                    for super_call in &constructor.node.super_calls {
+                        for arg in &super_call.args {
+                            finder.find_in_expression(arg);
+                        }
+                    }
+                    finder.find_in_block(&constructor.node.body, false);
+                    finder.scopes.clear();
+                }
+
+                finder.find_in_methods(&class_dec.methods);
+            }
+            TopLevelAstNode::EnumDec(_, enum_dec) => finder.find_in_methods(&enum_dec.methods),
+            TopLevelAstNode::InterfaceDec(_, interface_dec) => {
+                finder.find_in_methods(&interface_dec.methods)
+            }
+            TopLevelAstNode::FunctionDec(_, function_dec) => {
+                finder.find_in_body(&function_dec.args, &function_dec.body)
+            }
+            TopLevelAstNode::ConstDec(_, const_dec) => finder.find_in_expression(&const_dec.value),
+            _ => (),
+        }
+    }
+
+    (finder.found, finder.diagnostics)
+}
+
+/// This struct represents a variable (or `this`) that's in scope, where `span`
+/// is where it's declared.
+struct Binding {
+    span: Span,
+    is_mutable: bool,
+}
+
+/// Binds the arguments of a method or function. `this` is bound like any other
+/// argument, since it can't be used as a variable name.
+fn get_arg_scope<'a>(args: &[Documented<'a, AstMethodArgument<'a>>]) -> HashMap<&'a str, Binding> {
+    args.iter()
+        .map(|arg| match &arg.node {
+            AstMethodArgument::This(span) => (
+                "this",
+                Binding {
+                    span: span.clone(),
+                    is_mutable: false,
+                },
+            ),
+            AstMethodArgument::ThisMut(span) => (
+                "this",
+                Binding {
+                    span: span.clone(),
+                    is_mutable: true,
+                },
+            ),
+            AstMethodArgument::Regular(span, name, _) => (
+                *name,
+                Binding {
+                    span: span.clone(),
+                    is_mutable: false,
+                },
+            ),
+        })
+        .collect()
+}
+
+/// This struct represents a closure that is being walked over. `first_scope` is
+/// the index of the closure's own parameter scope, so any variable bound in a
+/// scope before it is a capture.
+struct OpenClosure<'a> {
+    first_scope: usize,
+    captures: ClosureCaptures<'a>,
+}
+
+/// This struct walks over bodies while keeping track of the variables in scope
+/// and the closures that are currently open.
+struct CaptureFinder<'a> {
+    scopes: Vec<HashMap<&'a str, Binding>>,
+    closures: Vec<OpenClosure<'a>>,
+    found: Vec<ClosureCaptures<'a>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> CaptureFinder<'a> {
+    fn find_in_methods<P>(&mut self, methods: &MethodList<'a, P>) {
+        for method in methods {
+            match method {
+                MethodOrConstraintAstNode::Method(_, method) => {
+                    if let Some(body) = &method.node.body {
+                        self.find_in_body(&method.node.args, body);
+                    }
+                }
+                MethodOrConstraintAstNode::Constraint(_, _, constrained_methods) => {
+                    self.find_in_methods(constrained_methods)
+                }
+            }
+        }
+    }
+
+    fn find_in_body(
+        &mut self,
+        args: &[Documented<'a, AstMethodArgument<'a>>],
+        body: &ExpressionBlockAstNode<'a>,
+    ) {
+        self.scopes = vec![get_arg_scope(args)];
+        self.find_in_block(body, false);
+        self.scopes.clear();
+    }
+
+    /// Walks over a block. If `is_value` is true, the block's value is moved out
+    /// of it (see `find_in_value`).
+    fn find_in_block(&mut self, block: &ExpressionBlockAstNode<'a>, is_value: bool) {
+        self.scopes.push(HashMap::new());
+
+        for statement in &block.statements {
+            match statement {
+                AstStatement::Expression(_, expression) => self.find_in_expression(expression),
+                AstStatement::VariableDec(_, variable_dec) => {
+                    if let Some(value) = &variable_dec.value {
+                        self.find_in_value(value);
+                    }
+                    self.bind_binding_pattern(&variable_dec.pattern);
+                }
+                AstStatement::Assignment(_, target, value) => {
+                    self.find_in_place(target);
+                    self.find_in_value(value);
+                }
+            }
+        }
+
+        if let Some(value) = &block.value {
+            if is_value {
+                self.find_in_value(value);
+            } else {
+                self.find_in_expression(value);
+            }
+        }
+
+        self.scopes.pop();
+    }
+
+    /// Walks over an expression whose value is stored or given back, which moves
+    /// it if it is just a variable.
+    fn find_in_value(&mut self, expression: &AstExpression<'a>) {
+        match expression {
+            AstExpression::Name(span, name) => self.use_variable(name, span, CaptureKind::ByMove),
+            AstExpression::This(span) => self.use_variable("this", span, CaptureKind::ByMove),
+            AstExpression::Parenthesized(_, inner) => self.find_in_value(inner),
+            AstExpression::Block(_, block) => self.find_in_block(block, true),
+            expression => self.find_in_expression(expression),
+        }
+    }
+
+    /// Walks over the target of an assignment, which mutates the variable that it
+    /// starts with.
+    fn find_in_place(&mut self, expression: &AstExpression<'a>) {
+        match expression {
+            AstExpression::Name(span, name) => {
+                self.use_variable(name, span, CaptureKind::ByMutableReference)
+            }
+            AstExpression::This(span) => {
+                self.use_variable("this", span, CaptureKind::ByMutableReference)
+            }
+            AstExpression::Parenthesized(_, inner) | AstExpression::MemberAccess(_, inner, _) => {
+                self.find_in_place(inner)
+            }
+            AstExpression::Subscript(_, target, index) => {
+                self.find_in_place(target);
+                self.find_in_expression(index);
+            }
+            expression => self.find_in_expression(expression),
+        }
+    }
+
+    fn find_in_expression(&mut self, expression: &AstExpression<'a>) {
+        match expression {
+            AstExpression::Literal(_, _)
+            | AstExpression::BigThis(_)
+            | AstExpression::Break(_, _)
+            | AstExpression::Continue(_, _) => (),
+
+            AstExpression::Name(span, name) => {
+                self.use_variable(name, span, CaptureKind::ByReference)
+            }
+            AstExpression::This(span) => self.use_variable("this", span, CaptureKind::ByReference),
+
+            AstExpression::Parenthesized(_, inner)
+            | AstExpression::Cast(_, inner, _)
+            | AstExpression::MemberAccess(_, inner, _)
+            | AstExpression::Unary(_, _, inner) => self.find_in_expression(inner),
+
+            AstExpression::Return(_, value) | AstExpression::Yield(_, value) => {
+                if let Some(value) = value {
+                    self.find_in_value(value);
+                }
+            }
+
+            AstExpression::Tuple(_, elements) | AstExpression::Array(_, elements) => {
+                for element in elements {
+                    self.find_in_expression(element);
+                }
+            }
+
+            AstExpression::Block(_, block) => self.find_in_block(block, false),
+
+            AstExpression::If(_, if_node) => {
+                self.find_in_expression(&if_node.condition);
+                self.find_in_block(&if_node.then_block, false);
+
+                if let Some(else_branch) = &if_node.else_branch {
+                    self.find_in_expression(else_branch);
+                }
+            }
+            AstExpression::While(_, while_node) => {
+                self.find_in_expression(&while_node.condition);
+                self.find_in_block(&while_node.body, false);
+            }
+            AstExpression::Loop(_, loop_node) => self.find_in_block(&loop_node.body, false),
+            AstExpression::For(_, for_node) => {
+                self.find_in_expression(&for_node.iterable);
+
+                self.scopes.push(HashMap::new());
+                self.bind_binding_pattern(&for_node.pattern);
+                self.find_in_block(&for_node.body, false);
+                self.scopes.pop();
+            }
+            AstExpression::Match(_, match_node) => {
+                self.find_in_expression(&match_node.scrutinee);
+
+                for arm in &match_node.arms {
+                    self.scopes.push(HashMap::new());
+                    self.bind_pattern(&arm.pattern);
+
+                    if let Some(guard) = &arm.guard {
+                        self.find_in_expression(guard);
+                    }
+                    self.find_in_expression(&arm.body);
+
+                    self.scopes.pop();
+                }
+            }
+
+            AstExpression::Closure(_, closure) => self.find_in_closure(closure),
+
+            AstExpression::Binary(_, _, left, right) => {
+                self.find_in_expression(left);
+                self.find_in_expression(right);
+            }
+            AstExpression::Subscript(_, target, index) => {
+                self.find_in_expression(target);
+                self.find_in_expression(index);
+            }
+            AstExpression::MacroCall(_, _, args) => {
+                for arg in args {
+                    self.find_in_expression(arg);
+                }
+            }
+            AstExpression::Call(_, callee, args) => {
+                self.find_in_expression(callee);
+                for arg in args {
+                    self.find_in_expression(arg);
+                }
+            }
+        }
+    }
+
+    fn find_in_closure(&mut self, closure: &ClosureAstNode<'a>) {
+        let param_scope = closure
+            .params
+            .iter()
+            .map(|param| {
+                (
+                    param.name,
+                    Binding {
+                        span: param.span.clone(),
+                        is_mutable: false,
+                    },
+                )
+            })
+            .collect();
+
+        self.closures.push(OpenClosure {
+            first_scope: self.scopes.len(),
+            captures: ClosureCaptures {
+                span: closure.span.clone(),
+                captures: Vec::new(),
+            },
+        });
+        self.scopes.push(param_scope);
+
+        match &closure.body {
+            AstClosureBody::Block(body) => self.find_in_block(body, true),
+            AstClosureBody::Expression(body) => self.find_in_value(body),
+        }
+
+        self.scopes.pop();
+        if let Some(closure) = self.closures.pop() {
+            self.found.push(closure.captures);
+        }
+    }
+
+    /// Records a use of a variable in every open closure that it's captured by.
+    fn use_variable(&mut self, name: &'a str, span: &Span, kind: CaptureKind) {
+        let (scope_idx, binding) = match self
+            .scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(scope_idx, scope)| Some((scope_idx, scope.get(name)?)))
+        {
+            Some(found) => found,
+            None => return,
+        };
+
+        let mut is_captured = false;
+
+        for closure in &mut self.closures {
+            if closure.first_scope <= scope_idx {
+                continue;
+            }
+            is_captured = true;
+
+            let captures = &mut closure.captures.captures;
+            match captures.iter_mut().find(|capture| capture.name == name) {
+                Some(capture) => capture.kind = capture.kind.max(kind),
+                None => captures.push(Capture {
+                    name,
+                    span: span.clone(),
+                    kind,
+                }),
+            }
+        }
+
+        if is_captured && kind == CaptureKind::ByMutableReference && !binding.is_mutable {
+            let diagnostic = if name == "this" {
+                Diagnostic::new(
+                    "`this` is captured by mutable reference, but this method only takes `this` (not `mut this`)"
+                        .to_string(),
+                    span.clone(),
+                )
+                .with_note(binding.span.clone(), "`this` is taken here".to_string())
+            } else {
+                Diagnostic::new(
+                    format!(
+                        "`{}` is captured by mutable reference, but it isn't declared with `mut`",
+                        name
+                    ),
+                    span.clone(),
+                )
+                .with_note(binding.span.clone(), format!("`{}` is declared here", name))
+            };
+
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn bind(&mut self, name: &'a str, span: &Span, is_mutable: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(
+                name,
+                Binding {
+                    span: span.clone(),
+                    is_mutable,
+                },
+            );
+        }
+    }
+
+    fn bind_binding_pattern(&mut self, pattern: &AstBindingPattern<'a>) {
+        match pattern {
+            AstBindingPattern::Name(span, name) => self.bind(name, span, false),
+            AstBindingPattern::MutableName(span, name) => self.bind(name, span, true),
+            AstBindingPattern::Ignored(_) => (),
+            AstBindingPattern::Tuple(_, elements) => elements
+                .iter()
+                .for_each(|element| self.bind_binding_pattern(element)),
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &AstPattern<'a>) {
+        match pattern {
+            AstPattern::Name(span, name) => self.bind(name, span, false),
+            AstPattern::MutableName(span, name) => self.bind(name, span, true),
+            AstPattern::Case(_, _, elements)
+            | AstPattern::Tuple(_, elements)
+            | AstPattern::Or(_, elements) => elements
+                .iter()
+                .for_each(|element| self.bind_pattern(element)),
+            _ => (),
+        }
+    }
+}
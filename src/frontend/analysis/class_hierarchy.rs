@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+
+use super::super::parser::lexer::logos_lexer::LexerToken;
+use super::super::parser::proper_parser::ast::attributes::AstTokenTree;
+use super::super::parser::proper_parser::ast::classes::{AstClassExtensibility, ClassDecAstNode};
+use super::super::parser::proper_parser::ast::expressions::AstExpression;
+use super::super::parser::proper_parser::ast::fields::ClassFieldAstNode;
+use super::super::parser::proper_parser::ast::methods::{
+    MethodAstNode, MethodList, MethodOrConstraintAstNode,
+};
+use super::super::parser::proper_parser::ast::publicity::AstClassItemPublicity;
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::super::parser::proper_parser::ast::types::NameAndGenericsAstNode;
+use super::diagnostic::Diagnostic;
+use super::walk::walk_file;
+
+/// This struct holds every class declared at the top level of a file, so that
+/// the rules about abstract, sealed, and final classes can be checked across
+/// the whole file.
+///
+/// (Classes that are declared in other files can't be checked yet.)
+pub struct ClassHierarchy<'a, 'b> {
+    classes: HashMap<&'a str, &'b ClassDecAstNode<'a>>,
+}
+
+impl<'a, 'b> ClassHierarchy<'a, 'b> {
+    /// Collects the classes (exported or not) from the top-level nodes of a file.
+    /// If a name is declared twice, the first class with it is kept.
+    pub fn collect(nodes: &'b [TopLevelAstNode<'a>]) -> Self {
+        let mut classes = HashMap::new();
+
+        for node in nodes {
+            if let TopLevelAstNode::ClassDec(_, class) = node.get_declaration() {
+                classes.entry(class.class_type.name).or_insert(class);
+            }
+        }
+
+        ClassHierarchy { classes }
+    }
+
+    /// Reports every class that extends a final class (or a sealed class that
+    /// doesn't permit it), every inherited field that can't be found in (exactly
+    /// one of) the superclasses, every abstract method that a non-abstract class
+    /// leaves unimplemented, and every place that an abstract class is
+    /// instantiated.
+    pub fn check(&self, nodes: &'b [TopLevelAstNode<'a>]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for class in self.sorted_classes() {
+            self.check_superclasses(class, &mut diagnostics);
+            self.check_inherited_fields(class, &mut diagnostics);
+
+            if !class.is_abstract {
+                self.check_abstract_methods(class, &mut diagnostics);
+            }
+        }
+
+        for node in nodes {
+            let abstract_methods = match node.get_declaration() {
+                TopLevelAstNode::StructDec(_, struct_dec) => {
+                    get_abstract_method_names(&struct_dec.methods)
+                }
+                TopLevelAstNode::EnumDec(_, enum_dec) => {
+                    get_abstract_method_names(&enum_dec.methods)
+                }
+                TopLevelAstNode::InterfaceDec(_, interface_dec) => {
+                    get_abstract_method_names(&interface_dec.methods)
+                }
+                _ => continue,
+            };
+
+            for method_name in abstract_methods {
+                diagnostics.push(Diagnostic::new(
+                    format!(
+                        "`{}` can't be abstract, since only abstract classes can have abstract methods",
+                        method_name.name
+                    ),
+                    method_name.span.clone(),
+                ));
+            }
+        }
+
+        walk_file(nodes, &mut |expression| {
+            if let AstExpression::Call(span, callee, _) = expression {
+                if let AstExpression::Name(_, name) = &**callee {
+                    match self.classes.get(name) {
+                        Some(class) if class.is_abstract => diagnostics.push(
+                            Diagnostic::new(
+                                format!("`{}` is abstract, so it can't be instantiated", name),
+                                span.clone(),
+                            )
+                            .with_note(
+                                class.class_type.span.clone(),
+                                format!("`{}` is declared here", name),
+                            ),
+                        ),
+                        _ => (),
+                    }
+                }
+            }
+        });
+
+        diagnostics
+    }
+
+    fn check_superclasses(&self, class: &ClassDecAstNode<'a>, diagnostics: &mut Vec<Diagnostic>) {
+        let class_name = class.class_type.name;
+
+        for superclass_type in &class.extends {
+            let superclass = match superclass_type
+                .get_name()
+                .and_then(|name| self.classes.get(name))
+            {
+                Some(superclass) => superclass,
+                None => continue,
+            };
+            let superclass_name = superclass.class_type.name;
+
+            match &superclass.extensibility {
+                AstClassExtensibility::Final(final_span) => diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "`{}` is final, so `{}` can't extend it",
+                            superclass_name, class_name
+                        ),
+                        superclass_type.get_span(),
+                    )
+                    .with_note(
+                        final_span.clone(),
+                        format!("`{}` is declared final here", superclass_name),
+                    ),
+                ),
+                AstClassExtensibility::Sealed(sealed_span, permits)
+                    if !permits.is_empty()
+                        && !permits
+                            .iter()
+                            .any(|permitted| permitted.get_name() == Some(class_name)) =>
+                {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            format!(
+                                "`{}` is sealed, and doesn't permit `{}` to extend it",
+                                superclass_name, class_name
+                            ),
+                            superclass_type.get_span(),
+                        )
+                        .with_note(
+                            sealed_span.clone(),
+                            format!("`{}` is declared sealed here", superclass_name),
+                        ),
+                    )
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Checks that every field that `class` inherits (by leaving out its type) can
+    /// be found in the superclass it comes from. Without an `@Inherit` attribute,
+    /// exactly one of the superclasses has to have a field with the same name.
+    fn check_inherited_fields(
+        &self,
+        class: &ClassDecAstNode<'a>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let class_name = class.class_type.name;
+        let superclass_names: Vec<_> = class
+            .extends
+            .iter()
+            .filter_map(|superclass_type| superclass_type.get_name())
+            .collect();
+
+        for field in &class.fields {
+            let field = match &field.node {
+                ClassFieldAstNode::Inherited(_, field) => field,
+                ClassFieldAstNode::Declared(..) => continue,
+            };
+
+            if let Some(attribute) = field
+                .attributes
+                .iter()
+                .find(|attribute| attribute.path == ["Inherit"])
+            {
+                let (superclass_name, inherited_name) = match get_inherit_args(&attribute.args) {
+                    Some(inherit_args) => inherit_args,
+                    None => {
+                        diagnostics.push(Diagnostic::new(
+                            "`@Inherit` has to be given the superclass to inherit from, like `@Inherit(MySuperClass)` or `@Inherit(MySuperClass.field)`"
+                                .to_string(),
+                            attribute.span.clone(),
+                        ));
+                        continue;
+                    }
+                };
+                let inherited_name = inherited_name.unwrap_or(field.name);
+
+                if !superclass_names.contains(&superclass_name) {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            format!(
+                                "`{}` can't be inherited from `{}`, since `{}` doesn't extend a class with that name",
+                                field.name, superclass_name, class_name
+                            ),
+                            attribute.span.clone(),
+                        )
+                        .with_note(
+                            class.class_type.span.clone(),
+                            format!("`{}` is declared here", class_name),
+                        ),
+                    );
+                } else if self.has_field(superclass_name, inherited_name, &mut vec![class_name])
+                    == Some(false)
+                {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            format!(
+                                "`{}` doesn't have a field named `{}` to inherit",
+                                superclass_name, inherited_name
+                            ),
+                            attribute.span.clone(),
+                        )
+                        .with_note(
+                            self.classes[superclass_name].class_type.span.clone(),
+                            format!("`{}` is declared here", superclass_name),
+                        ),
+                    );
+                }
+
+                continue;
+            }
+
+            // (If one of the superclasses isn't declared in this file, the field
+            // might come from it, so it can only be reported if it's ambiguous.)
+            let mut is_checkable = superclass_names.len() == class.extends.len();
+            let mut owner_names = Vec::new();
+
+            for &superclass_name in &superclass_names {
+                match self.has_field(superclass_name, field.name, &mut vec![class_name]) {
+                    Some(true) => owner_names.push(superclass_name),
+                    Some(false) => (),
+                    None => is_checkable = false,
+                }
+            }
+
+            match owner_names[..] {
+                [] if is_checkable => diagnostics.push(Diagnostic::new(
+                    format!(
+                        "`{}` leaves out its type, but none of the classes that `{}` extends have a field named `{}` to inherit",
+                        field.name, class_name, field.name
+                    ),
+                    field.span.clone(),
+                )),
+                [] | [_] => (),
+                [first_owner_name, ..] => diagnostics.push(Diagnostic::new(
+                    format!(
+                        "`{}` could be inherited from any of {}, so it needs an attribute like `@Inherit({})` to pick one",
+                        field.name,
+                        owner_names
+                            .iter()
+                            .map(|owner_name| format!("`{}`", owner_name))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        first_owner_name
+                    ),
+                    field.span.clone(),
+                )),
+            }
+        }
+    }
+
+    /// Checks whether the class named `class_name` has a field named `field_name`,
+    /// either by declaring it or by inheriting it. Returns `None` if that can't be
+    /// known, because one of the classes involved isn't declared in this file.
+    ///
+    /// `path` holds the classes that are already being looked at, so that a class
+    /// that (wrongly) extends itself doesn't loop forever.
+    fn has_field(
+        &self,
+        class_name: &'a str,
+        field_name: &str,
+        path: &mut Vec<&'a str>,
+    ) -> Option<bool> {
+        if path.contains(&class_name) {
+            return Some(false);
+        }
+
+        let class = self.classes.get(class_name)?;
+
+        if class
+            .fields
+            .iter()
+            .any(|field| field.node.get_name() == field_name)
+        {
+            return Some(true);
+        }
+
+        path.push(class_name);
+
+        let mut has_field = Some(false);
+
+        for superclass_type in &class.extends {
+            match superclass_type
+                .get_name()
+                .and_then(|name| self.has_field(name, field_name, path))
+            {
+                Some(true) => {
+                    has_field = Some(true);
+                    break;
+                }
+                Some(false) => (),
+                None => has_field = None,
+            }
+        }
+
+        path.pop();
+
+        has_field
+    }
+
+    fn check_abstract_methods(
+        &self,
+        class: &ClassDecAstNode<'a>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let class_name = class.class_type.name;
+
+        for (owner_name, method) in self.get_unimplemented_abstract_methods(class, &mut Vec::new())
+        {
+            let method_name = method.new_type.name;
+
+            let diagnostic = if owner_name == class_name {
+                Diagnostic::new(
+                    format!(
+                        "`{}` is abstract, but `{}` isn't an abstract class",
+                        method_name, class_name
+                    ),
+                    method.new_type.span.clone(),
+                )
+                .with_note(
+                    class.class_type.span.clone(),
+                    format!("`{}` is declared here", class_name),
+                )
+            } else {
+                Diagnostic::new(
+                    format!(
+                        "`{}` doesn't implement the abstract method `{}` from `{}`",
+                        class_name, method_name, owner_name
+                    ),
+                    class.class_type.span.clone(),
+                )
+                .with_note(
+                    method.new_type.span.clone(),
+                    format!("`{}` is declared here", method_name),
+                )
+            };
+
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Gets the abstract methods that are still unimplemented in `class`, along
+    /// with the name of the class that declared each one.
+    ///
+    /// `path` holds the classes that are already being looked at, so that a class
+    /// that (wrongly) extends itself doesn't loop forever.
+    fn get_unimplemented_abstract_methods(
+        &self,
+        class: &'b ClassDecAstNode<'a>,
+        path: &mut Vec<&'a str>,
+    ) -> Vec<(&'a str, &'b MethodAstNode<'a, AstClassItemPublicity>)> {
+        let class_name = class.class_type.name;
+        path.push(class_name);
+
+        let mut abstract_methods: Vec<(&'a str, &'b MethodAstNode<'a, _>)> = Vec::new();
+
+        for superclass in class
+            .extends
+            .iter()
+            .filter_map(|superclass_type| superclass_type.get_name())
+            .filter(|name| !path.contains(name))
+            .filter_map(|name| self.classes.get(name).copied())
+            .collect::<Vec<_>>()
+        {
+            for (owner_name, method) in self.get_unimplemented_abstract_methods(superclass, path) {
+                if !abstract_methods
+                    .iter()
+                    .any(|(_, found)| found.new_type.name == method.new_type.name)
+                {
+                    abstract_methods.push((owner_name, method));
+                }
+            }
+        }
+
+        for method in get_methods(&class.methods) {
+            abstract_methods.retain(|(_, found)| found.new_type.name != method.new_type.name);
+
+            if method.is_abstract {
+                abstract_methods.push((class_name, method));
+            }
+        }
+
+        path.pop();
+
+        abstract_methods
+    }
+
+    /// Gets the classes in the order they were declared, so that diagnostics come
+    /// out in the same order every time.
+    fn sorted_classes(&self) -> Vec<&'b ClassDecAstNode<'a>> {
+        let mut classes: Vec<_> = self.classes.values().copied().collect();
+        classes.sort_by_key(|class| class.span.start);
+        classes
+    }
+}
+
+/// Gets the superclass (and the field in it, if one is given) that the arguments
+/// of an `@Inherit(MySuperClass)` or `@Inherit(MySuperClass.field)` attribute
+/// name.
+fn get_inherit_args<'a>(args: &[AstTokenTree<'a>]) -> Option<(&'a str, Option<&'a str>)> {
+    match args {
+        [AstTokenTree::Token(_, LexerToken::Identifier(superclass_name))] => {
+            Some((superclass_name, None))
+        }
+        [AstTokenTree::Token(_, LexerToken::Identifier(superclass_name)), AstTokenTree::Token(_, LexerToken::MemberAccess), AstTokenTree::Token(_, LexerToken::Identifier(field_name))] => {
+            Some((superclass_name, Some(field_name)))
+        }
+        _ => None,
+    }
+}
+
+/// Gets the names of the abstract methods in a method list.
+fn get_abstract_method_names<'a, 'b, P>(
+    methods: &'b MethodList<'a, P>,
+) -> Vec<&'b NameAndGenericsAstNode<'a>> {
+    get_methods(methods)
+        .into_iter()
+        .filter(|method| method.is_abstract)
+        .map(|method| &method.new_type)
+        .collect()
+}
+
+/// Gets every method in a method list, including the ones inside of constraint
+/// blocks.
+fn get_methods<'a, 'b, P>(methods: &'b MethodList<'a, P>) -> Vec<&'b MethodAstNode<'a, P>> {
+    let mut found_methods = Vec::new();
+
+    for method in methods {
+        match method {
+            MethodOrConstraintAstNode::Method(_, method) => found_methods.push(&method.node),
+            MethodOrConstraintAstNode::Constraint(_, _, constrained_methods) => {
+                found_methods.append(&mut get_methods(constrained_methods))
+            }
+        }
+    }
+
+    found_methods
+}
@@ -0,0 +1,63 @@
+use logos::Span;
+
+use super::super::parser::proper_parser::ast::constants::AssociatedConstList;
+use super::super::parser::proper_parser::ast::expressions::AstExpression;
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::diagnostic::Diagnostic;
+
+/// Checks that the value of every module-level constant, and of every constant
+/// that belongs to a struct, class, or enum, could be evaluated at compile time
+/// (see [`AstExpression::is_constant`]).
+///
+/// (A `const` inside of a body is just a variable that can't be reassigned, so
+/// its value can be anything.)
+pub fn check_constants(nodes: &[TopLevelAstNode]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for node in nodes {
+        match node.get_declaration() {
+            TopLevelAstNode::ConstDec(_, const_dec) => check_value(
+                &const_dec.value,
+                const_dec.pattern.get_span(),
+                &mut diagnostics,
+            ),
+            TopLevelAstNode::StructDec(_, struct_dec) => {
+                check_associated_consts(&struct_dec.consts, &mut diagnostics)
+            }
+            TopLevelAstNode::ClassDec(_, class_dec) => {
+                check_associated_consts(&class_dec.consts, &mut diagnostics)
+            }
+            TopLevelAstNode::EnumDec(_, enum_dec) => {
+                check_associated_consts(&enum_dec.consts, &mut diagnostics)
+            }
+            _ => (),
+        }
+    }
+
+    diagnostics
+}
+
+fn check_associated_consts<P>(consts: &AssociatedConstList<P>, diagnostics: &mut Vec<Diagnostic>) {
+    for associated_const in consts {
+        check_value(
+            &associated_const.node.value,
+            associated_const.node.span.clone(),
+            diagnostics,
+        );
+    }
+}
+
+/// Reports `value` if it isn't constant. `declaration_span` is where the
+/// constant that it belongs to is declared.
+fn check_value(value: &AstExpression, declaration_span: Span, diagnostics: &mut Vec<Diagnostic>) {
+    if !value.is_constant() {
+        diagnostics.push(
+            Diagnostic::new(
+                "the value of a constant has to be built out of literals, other constants, and operators, so that it can be evaluated at compile time"
+                    .to_string(),
+                value.get_span(),
+            )
+            .with_note(declaration_span, "the constant is declared here".to_string()),
+        );
+    }
+}
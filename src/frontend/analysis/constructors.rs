@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet};
+
+use logos::Span;
+
+use super::super::parser::proper_parser::ast::classes::ClassDecAstNode;
+use super::super::parser::proper_parser::ast::expressions::AstExpression;
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::diagnostic::Diagnostic;
+use super::walk::walk_file_in_scope;
+
+/// Checks that every `super(...)` call in a class's constructors calls the
+/// constructor of a class that it actually extends, and that no superclass's
+/// constructor is called twice by the same constructor.
+///
+/// Constructions of the structs and classes declared in the file (like
+/// `Point(1, 2)`) are checked against their constructors as well.
+pub fn check_constructors(nodes: &[TopLevelAstNode]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for node in nodes {
+        if let TopLevelAstNode::ClassDec(_, class) = node.get_declaration() {
+            check_class_constructors(class, &mut diagnostics);
+        }
+    }
+
+    check_constructions(nodes, &mut diagnostics);
+
+    diagnostics
+}
+
+/// This enum represents the constructors that a struct or class can be built
+/// with, along with where the type is declared.
+enum Constructors {
+    /// A struct's memberwise constructor, which takes one argument for each
+    /// field without a default.
+    Memberwise(usize, Span),
+    /// The argument counts and spans of a class's declared constructors. A class
+    /// that doesn't declare any is constructed without arguments.
+    Declared(Vec<(usize, Span)>, Span),
+}
+
+/// Makes sure that every construction of a struct or class (a call to its name
+/// that isn't shadowed by a variable or a function) gives one of its
+/// constructors the right number of arguments. If a type name is declared
+/// twice, the first type with it is used.
+fn check_constructions(nodes: &[TopLevelAstNode], diagnostics: &mut Vec<Diagnostic>) {
+    let mut constructible_types = HashMap::new();
+    let mut function_names = HashSet::new();
+
+    for node in nodes {
+        let (type_name, constructors) = match node.get_declaration() {
+            TopLevelAstNode::StructDec(_, struct_dec) => (
+                struct_dec.struct_type.name,
+                Constructors::Memberwise(
+                    struct_dec.memberwise_constructor().args.len(),
+                    struct_dec.struct_type.span.clone(),
+                ),
+            ),
+            TopLevelAstNode::ClassDec(_, class_dec) => (
+                class_dec.class_type.name,
+                Constructors::Declared(
+                    class_dec
+                        .constructors
+                        .iter()
+                        .map(|constructor| {
+                            (constructor.node.args.len(), constructor.node.span.clone())
+                        })
+                        .collect(),
+                    class_dec.class_type.span.clone(),
+                ),
+            ),
+            TopLevelAstNode::FunctionDec(_, function_dec) => {
+                function_names.insert(function_dec.function_type.name);
+                continue;
+            }
+            _ => continue,
+        };
+
+        constructible_types.entry(type_name).or_insert(constructors);
+    }
+
+    walk_file_in_scope(nodes, &mut |expression, locals| {
+        let (span, name, arg_count) = match expression {
+            AstExpression::Call(span, callee, args) => match &**callee {
+                AstExpression::Name(_, name)
+                    if !locals.contains(name) && !function_names.contains(name) =>
+                {
+                    (span, *name, args.len())
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+
+        match constructible_types.get(name) {
+            Some(Constructors::Memberwise(expected_count, struct_span))
+                if *expected_count != arg_count =>
+            {
+                diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "`{}`'s memberwise constructor takes {} argument(s) (one for each field without a default), but {} were given",
+                            name, expected_count, arg_count
+                        ),
+                        span.clone(),
+                    )
+                    .with_note(struct_span.clone(), format!("`{}` is declared here", name)),
+                )
+            }
+            Some(Constructors::Declared(constructors, class_span))
+                if constructors.is_empty() && arg_count != 0 =>
+            {
+                diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "`{}` doesn't declare any constructors, so it takes no arguments, but {} were given",
+                            name, arg_count
+                        ),
+                        span.clone(),
+                    )
+                    .with_note(class_span.clone(), format!("`{}` is declared here", name)),
+                )
+            }
+            Some(Constructors::Declared(constructors, _))
+                if !constructors.is_empty()
+                    && constructors
+                        .iter()
+                        .all(|(expected_count, _)| *expected_count != arg_count) =>
+            {
+                let mut diagnostic = Diagnostic::new(
+                    format!(
+                        "`{}` doesn't have a constructor that takes {} argument(s)",
+                        name, arg_count
+                    ),
+                    span.clone(),
+                );
+
+                for (expected_count, constructor_span) in constructors {
+                    diagnostic = diagnostic.with_note(
+                        constructor_span.clone(),
+                        format!("this constructor takes {} argument(s)", expected_count),
+                    );
+                }
+
+                diagnostics.push(diagnostic);
+            }
+            _ => (),
+        }
+    });
+}
+
+fn check_class_constructors(class: &ClassDecAstNode, diagnostics: &mut Vec<Diagnostic>) {
+    let class_name = class.class_type.name;
+
+    for constructor in &class.constructors {
+        let mut called_parents: Vec<(&str, Span)> = Vec::new();
+
+        for super_call in &constructor.node.super_calls {
+            let parent_name = match (&super_call.parent, class.extends.as_slice()) {
+                (Some(parent), _) => match parent.get_name() {
+                    Some(parent_name)
+                        if class
+                            .extends
+                            .iter()
+                            .any(|superclass| superclass.get_name() == Some(parent_name)) =>
+                    {
+                        parent_name
+                    }
+                    _ => {
+                        let parent_span = parent.get_span();
+                        diagnostics.push(
+                            Diagnostic::new(
+                                format!("this isn't a class that `{}` extends", class_name),
+                                parent_span,
+                            )
+                            .with_note(
+                                class.class_type.span.clone(),
+                                format!("`{}` is declared here", class_name),
+                            ),
+                        );
+                        continue;
+                    }
+                },
+                (None, [superclass]) => match superclass.get_name() {
+                    Some(parent_name) => parent_name,
+                    None => continue,
+                },
+                (None, []) => {
+                    diagnostics.push(Diagnostic::new(
+                        format!(
+                            "`{}` doesn't extend any classes, so it has no superclass constructor to call",
+                            class_name
+                        ),
+                        super_call.span.clone(),
+                    ));
+                    continue;
+                }
+                (None, _) => {
+                    diagnostics.push(Diagnostic::new(
+                        format!(
+                            "`{}` extends more than one class, so `super` has to name the one it calls (as in `super<Parent>(...)`)",
+                            class_name
+                        ),
+                        super_call.span.clone(),
+                    ));
+                    continue;
+                }
+            };
+
+            match called_parents
+                .iter()
+                .find(|(called_parent, _)| *called_parent == parent_name)
+            {
+                Some((_, first_call_span)) => diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "the constructor of `{}` is called more than once",
+                            parent_name
+                        ),
+                        super_call.span.clone(),
+                    )
+                    .with_note(
+                        first_call_span.clone(),
+                        format!("`{}`'s constructor is first called here", parent_name),
+                    ),
+                ),
+                None => called_parents.push((parent_name, super_call.span.clone())),
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use logos::Span;
+
+/// This struct represents a problem found while analyzing an AST (rather than
+/// while parsing it, which is what `ParseError` is for).
+///
+/// `notes` point at other places in the source that help explain the problem.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+
+    pub notes: Vec<(Span, String)>,
+}
+
+impl Diagnostic {
+    pub fn new(message: String, span: Span) -> Self {
+        Diagnostic {
+            message,
+            span,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_note(mut self, span: Span, note: String) -> Self {
+        self.notes.push((span, note));
+        self
+    }
+}
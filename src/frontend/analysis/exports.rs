@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+
+use logos::Span;
+
+use super::super::parser::proper_parser::ast::imports_exports::{
+    AstExportedItems, AstImportedItems, AstModuleLocation,
+};
+use super::super::parser::proper_parser::ast::patterns::{
+    AstBindingPattern, AstDestructuringPattern,
+};
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::diagnostic::Diagnostic;
+
+/// Checks the exports of a file. Every item in an export list without `from`
+/// has to be declared or imported in the file, and no two exports can have the
+/// same name.
+///
+/// (If the file has a glob import, any name could have come from it, so only the
+/// second check is done.)
+pub fn check_exports(nodes: &[TopLevelAstNode]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut local_names = HashSet::new();
+    let mut has_glob_import = false;
+
+    for node in nodes {
+        match node.get_declaration() {
+            TopLevelAstNode::ImportFrom(_, import_statement) => {
+                match &import_statement.imported_items {
+                    AstImportedItems::Destructured(pattern) => local_names.extend(
+                        get_destructured_names(pattern, None)
+                            .into_iter()
+                            .map(|(name, _)| name),
+                    ),
+                    AstImportedItems::Glob(_) => has_glob_import = true,
+                    AstImportedItems::Module(_, alias) => local_names.extend(
+                        alias.or_else(|| get_module_name(&import_statement.module_location)),
+                    ),
+                }
+            }
+            declaration => local_names.extend(
+                get_declared_names(declaration)
+                    .into_iter()
+                    .map(|(name, _)| name),
+            ),
+        }
+    }
+
+    let mut exported_names: HashMap<&str, Span> = HashMap::new();
+
+    for node in nodes {
+        let names = match node {
+            TopLevelAstNode::Export(_, exported) => get_declared_names(exported.get_declaration()),
+            TopLevelAstNode::ExportItems(_, export_statement) => {
+                let patterns = match &export_statement.exported_items {
+                    AstExportedItems::List(_, patterns) => patterns,
+                    AstExportedItems::Glob(_) => continue,
+                };
+
+                if export_statement.module_location.is_none() && !has_glob_import {
+                    for pattern in patterns {
+                        let (name, span) = get_source_name(pattern);
+
+                        if !local_names.contains(name) {
+                            diagnostics.push(Diagnostic::new(
+                                format!(
+                                    "`{}` is exported, but nothing named `{}` is declared or imported in this file",
+                                    name, name
+                                ),
+                                span,
+                            ));
+                        }
+                    }
+                }
+
+                patterns
+                    .iter()
+                    .flat_map(|pattern| get_destructured_names(pattern, None))
+                    .collect()
+            }
+            _ => continue,
+        };
+
+        for (name, span) in names {
+            match exported_names.get(name) {
+                Some(first_span) => diagnostics.push(
+                    Diagnostic::new(format!("`{}` is exported more than once", name), span)
+                        .with_note(
+                            first_span.clone(),
+                            format!("`{}` is first exported here", name),
+                        ),
+                ),
+                None => {
+                    exported_names.insert(name, span);
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Gets the names (and the spans of the names) that a declaration introduces.
+fn get_declared_names<'a>(declaration: &TopLevelAstNode<'a>) -> Vec<(&'a str, Span)> {
+    let name_and_generics = match declaration {
+        TopLevelAstNode::EnumDec(_, dec) => &dec.enum_type,
+        TopLevelAstNode::StructDec(_, dec) => &dec.struct_type,
+        TopLevelAstNode::ClassDec(_, dec) => &dec.class_type,
+        TopLevelAstNode::InterfaceDec(_, dec) => &dec.interface_type,
+        TopLevelAstNode::FunctionDec(_, dec) => &dec.function_type,
+        TopLevelAstNode::TypeAlias(_, dec) => &dec.aliased_type,
+        TopLevelAstNode::ConstDec(_, dec) => {
+            let mut names = Vec::new();
+            get_bound_names(&dec.pattern, &mut names);
+            return names;
+        }
+        _ => return Vec::new(),
+    };
+
+    vec![(name_and_generics.name, name_and_generics.span.clone())]
+}
+
+fn get_bound_names<'a>(pattern: &AstBindingPattern<'a>, names: &mut Vec<(&'a str, Span)>) {
+    match pattern {
+        AstBindingPattern::Name(span, name) | AstBindingPattern::MutableName(span, name) => {
+            names.push((name, span.clone()))
+        }
+        AstBindingPattern::Ignored(_) => (),
+        AstBindingPattern::Tuple(_, elements) => elements
+            .iter()
+            .for_each(|element| get_bound_names(element, names)),
+    }
+}
+
+/// Gets the names that a destructuring pattern brings in (or makes available,
+/// when exporting). `parent` is the name of the pattern this one is nested in,
+/// which `self` refers to.
+fn get_destructured_names<'a>(
+    pattern: &AstDestructuringPattern<'a>,
+    parent: Option<&'a str>,
+) -> Vec<(&'a str, Span)> {
+    match pattern {
+        AstDestructuringPattern::Name(span, "self") => parent
+            .map(|parent| (parent, span.clone()))
+            .into_iter()
+            .collect(),
+        AstDestructuringPattern::Name(span, name)
+        | AstDestructuringPattern::AliasedName(span, _, name) => vec![(name, span.clone())],
+        AstDestructuringPattern::Destructured(_, name, children) => children
+            .iter()
+            .flat_map(|child| get_destructured_names(child, Some(name)))
+            .collect(),
+    }
+}
+
+/// Gets the name that the outermost part of a destructuring pattern refers to.
+fn get_source_name<'a>(pattern: &AstDestructuringPattern<'a>) -> (&'a str, Span) {
+    match pattern {
+        AstDestructuringPattern::Name(span, name)
+        | AstDestructuringPattern::AliasedName(span, name, _)
+        | AstDestructuringPattern::Destructured(span, name, _) => (name, span.clone()),
+    }
+}
+
+/// Gets the name that importing a whole module binds it to, if it doesn't have
+/// an alias. (Relative roots like `super` don't have a name of their own.)
+fn get_module_name<'a>(module_location: &AstModuleLocation<'a>) -> Option<&'a str> {
+    match module_location {
+        AstModuleLocation::Root(_, name) | AstModuleLocation::MemberOf(_, _, name) => Some(name),
+        AstModuleLocation::File(_, path) => Some(path.trim_end_matches(".uck")),
+        AstModuleLocation::CurrentModule(_)
+        | AstModuleLocation::Super(_, _)
+        | AstModuleLocation::Package(_) => None,
+    }
+}
@@ -0,0 +1,653 @@
+use std::collections::HashMap;
+
+use logos::Span;
+
+use super::super::parser::proper_parser::ast::documentation::Documented;
+use super::super::parser::proper_parser::ast::expressions::{
+    AstBinaryOperator, AstClosureBody, AstExpression, AstStatement, AstUnaryOperator,
+    ExpressionBlockAstNode,
+};
+use super::super::parser::proper_parser::ast::methods::{
+    AstMethodArgument, AstOverloadableOperator, MethodAstNode, MethodList,
+    MethodOrConstraintAstNode,
+};
+use super::super::parser::proper_parser::ast::patterns::{AstBindingPattern, AstPattern};
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::super::parser::proper_parser::ast::types::{AstType, NameAndGenericsAstNode};
+use super::diagnostic::Diagnostic;
+
+/// This struct represents a single operator overload that a type declares.
+pub struct OperatorOverload<'a, 'b> {
+    pub name: &'b NameAndGenericsAstNode<'a>,
+    /// The number of arguments the overload takes after `this`.
+    pub arg_count: usize,
+    pub return_type: Option<&'b AstType<'a>>,
+}
+
+/// This struct holds the operator overloads of a single struct, class, or enum,
+/// along with the classes it extends (which it inherits overloads from).
+struct OverloadingType<'a, 'b> {
+    overloads: HashMap<AstOverloadableOperator, OperatorOverload<'a, 'b>>,
+    parents: Vec<&'a str>,
+}
+
+/// This struct represents an operator expression that uses an overload.
+/// `type_name` is the type that declares the overload, which is either the type
+/// of the (left) operand or a class that it extends.
+#[derive(Debug)]
+pub struct ResolvedOperator<'a> {
+    pub span: Span,
+    pub operator: AstOverloadableOperator,
+    pub type_name: &'a str,
+}
+
+/// This struct holds the operator overloads of every struct, class, and enum
+/// declared at the top level of a file, so that operator expressions can be
+/// resolved to them.
+///
+/// (Types that are declared in other files can't be resolved yet.)
+pub struct OperatorOverloads<'a, 'b> {
+    types: HashMap<&'a str, OverloadingType<'a, 'b>>,
+}
+
+impl<'a, 'b> OperatorOverloads<'a, 'b> {
+    /// Collects the operator overloads from the top-level nodes of a file,
+    /// reporting any that take the wrong arguments or that are declared twice
+    /// for the same type. If a type name is declared twice, the first type with
+    /// it is kept.
+    pub fn collect(nodes: &'b [TopLevelAstNode<'a>]) -> (Self, Vec<Diagnostic>) {
+        let mut types = HashMap::new();
+        let mut diagnostics = Vec::new();
+
+        for node in nodes {
+            let (type_name, overloads, parents) = match node.get_declaration() {
+                TopLevelAstNode::StructDec(_, struct_dec) => (
+                    struct_dec.struct_type.name,
+                    collect_overloads(
+                        &struct_dec.struct_type,
+                        &struct_dec.methods,
+                        &mut diagnostics,
+                    ),
+                    Vec::new(),
+                ),
+                TopLevelAstNode::ClassDec(_, class_dec) => (
+                    class_dec.class_type.name,
+                    collect_overloads(&class_dec.class_type, &class_dec.methods, &mut diagnostics),
+                    class_dec
+                        .extends
+                        .iter()
+                        .filter_map(AstType::get_name)
+                        .collect(),
+                ),
+                TopLevelAstNode::EnumDec(_, enum_dec) => (
+                    enum_dec.enum_type.name,
+                    collect_overloads(&enum_dec.enum_type, &enum_dec.methods, &mut diagnostics),
+                    Vec::new(),
+                ),
+                _ => continue,
+            };
+
+            types
+                .entry(type_name)
+                .or_insert(OverloadingType { overloads, parents });
+        }
+
+        (OperatorOverloads { types }, diagnostics)
+    }
+
+    /// Gets the overload of `operator` that `type_name` uses (either its own, or
+    /// the first one found in the classes it extends), along with the name of
+    /// the type that declares it.
+    pub fn get(
+        &self,
+        type_name: &'a str,
+        operator: AstOverloadableOperator,
+    ) -> Option<(&'a str, &OperatorOverload<'a, 'b>)> {
+        self.get_from(type_name, operator, &mut Vec::new())
+    }
+
+    fn get_from(
+        &self,
+        type_name: &'a str,
+        operator: AstOverloadableOperator,
+        visited: &mut Vec<&'a str>,
+    ) -> Option<(&'a str, &OperatorOverload<'a, 'b>)> {
+        // Cycles in the hierarchy are reported elsewhere, so they're just cut off
+        // here.
+        if visited.contains(&type_name) {
+            return None;
+        }
+        visited.push(type_name);
+
+        let overloading_type = self.types.get(type_name)?;
+
+        match overloading_type.overloads.get(&operator) {
+            Some(overload) => Some((type_name, overload)),
+            None => overloading_type
+                .parents
+                .iter()
+                .find_map(|parent| self.get_from(parent, operator, visited)),
+        }
+    }
+
+    /// Resolves every operator expression whose operand is known to be one of the
+    /// collected types to the overload it uses, reporting calls that give an
+    /// `operator()` the wrong number of arguments.
+    ///
+    /// The type of an operand is only known if it is `this`, an argument or
+    /// variable with a declared type, a call to a type's constructor, or the
+    /// result of another overload. Operators that a type doesn't overload itself
+    /// are left alone, since they could come from a derive or an interface.
+    pub fn resolve(
+        &self,
+        nodes: &'b [TopLevelAstNode<'a>],
+    ) -> (Vec<ResolvedOperator<'a>>, Vec<Diagnostic>) {
+        let mut resolver = OperatorResolver {
+            overloads: self,
+            this_type: None,
+            scopes: Vec::new(),
+            resolved: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        for node in nodes {
+            match node.get_declaration() {
+                TopLevelAstNode::StructDec(_, struct_dec) => {
+                    resolver.resolve_methods(Some(struct_dec.struct_type.name), &struct_dec.methods)
+                }
+                TopLevelAstNode::ClassDec(_, class_dec) => {
+                    for constructor in &class_dec.constructors {
+                        resolver.resolve_body(
+                            Some(class_dec.class_type.name),
+                            &constructor.node.args,
+                            &constructor.node.body,
+                        );
+                    }
+
+                    resolver.resolve_methods(Some(class_dec.class_type.name), &class_dec.methods);
+                }
+                TopLevelAstNode::EnumDec(_, enum_dec) => {
+                    resolver.resolve_methods(Some(enum_dec.enum_type.name), &enum_dec.methods)
+                }
+                TopLevelAstNode::InterfaceDec(_, interface_dec) => {
+                    resolver.resolve_methods(None, &interface_dec.methods)
+                }
+                TopLevelAstNode::FunctionDec(_, function_dec) => {
+                    resolver.resolve_body(None, &function_dec.args, &function_dec.body)
+                }
+                TopLevelAstNode::ConstDec(_, const_dec) => {
+                    resolver.resolve_expression(&const_dec.value);
+                }
+                _ => (),
+            }
+        }
+
+        (resolver.resolved, resolver.diagnostics)
+    }
+}
+
+/// Collects the operator overloads of a single type, checking the arguments of
+/// each one.
+///
+/// Overloads inside of different constraint blocks are allowed to overlap, so
+/// only the ones outside of any are checked for duplicates.
+fn collect_overloads<'a, 'b, P>(
+    type_name: &'b NameAndGenericsAstNode<'a>,
+    methods: &'b MethodList<'a, P>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> HashMap<AstOverloadableOperator, OperatorOverload<'a, 'b>> {
+    let mut overloads = HashMap::new();
+    let mut unconstrained_names = HashMap::new();
+
+    for (method, is_constrained) in get_methods(methods, false) {
+        let operator = match method.operator {
+            Some(operator) => operator,
+            None => continue,
+        };
+
+        if let Some(diagnostic) = check_overload_args(method, operator) {
+            diagnostics.push(diagnostic);
+        }
+
+        if !is_constrained {
+            if let Some(first_name) = unconstrained_names.insert(operator, &method.new_type) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        format!(
+                            "`{}` overloads `{}` more than once",
+                            type_name.name,
+                            operator.get_symbol()
+                        ),
+                        method.new_type.span.clone(),
+                    )
+                    .with_note(
+                        first_name.span.clone(),
+                        format!("`{}` is first overloaded here", operator.get_symbol()),
+                    ),
+                );
+            }
+        }
+
+        overloads.entry(operator).or_insert(OperatorOverload {
+            name: &method.new_type,
+            arg_count: method.args.len().saturating_sub(1),
+            return_type: method.return_type.as_ref(),
+        });
+    }
+
+    overloads
+}
+
+/// Makes sure that an overload takes `this` (or `mut this`) first, followed by
+/// as many other arguments as its operator has operands.
+fn check_overload_args<P>(
+    method: &MethodAstNode<P>,
+    operator: AstOverloadableOperator,
+) -> Option<Diagnostic> {
+    let takes_this = matches!(
+        method.args.first().map(|arg| &arg.node),
+        Some(AstMethodArgument::This(_) | AstMethodArgument::ThisMut(_))
+    );
+
+    if !takes_this {
+        return Some(Diagnostic::new(
+            format!(
+                "`{}` has to take `this` (or `mut this`) as its first argument",
+                operator.get_method_name()
+            ),
+            method.new_type.span.clone(),
+        ));
+    }
+
+    let other_arg_count = method.args.len() - 1;
+
+    let (is_valid, expected) = match operator {
+        AstOverloadableOperator::Unary(_) => (other_arg_count == 0, "no other arguments"),
+        AstOverloadableOperator::Binary(_) => (other_arg_count == 1, "exactly one other argument"),
+        AstOverloadableOperator::Subscript => (other_arg_count >= 1, "at least one other argument"),
+        AstOverloadableOperator::Call => (true, ""),
+    };
+
+    if is_valid {
+        None
+    } else {
+        Some(Diagnostic::new(
+            format!(
+                "`{}` has to take {} after `this`, but it takes {}",
+                operator.get_method_name(),
+                expected,
+                other_arg_count
+            ),
+            method.new_type.span.clone(),
+        ))
+    }
+}
+
+/// Gets every method in a method list (including the ones inside of constraint
+/// blocks), along with whether or not each one is inside of a constraint block.
+fn get_methods<'a, 'b, P>(
+    methods: &'b MethodList<'a, P>,
+    is_constrained: bool,
+) -> Vec<(&'b MethodAstNode<'a, P>, bool)> {
+    let mut found_methods = Vec::new();
+
+    for method in methods {
+        match method {
+            MethodOrConstraintAstNode::Method(_, method) => {
+                found_methods.push((&method.node, is_constrained))
+            }
+            MethodOrConstraintAstNode::Constraint(_, _, constrained_methods) => {
+                found_methods.append(&mut get_methods(constrained_methods, true))
+            }
+        }
+    }
+
+    found_methods
+}
+
+/// This struct walks over method bodies while keeping track of the types of the
+/// variables in scope (`None` means that a variable's type isn't known).
+struct OperatorResolver<'a, 'b, 'c> {
+    overloads: &'c OperatorOverloads<'a, 'b>,
+    this_type: Option<&'a str>,
+    scopes: Vec<HashMap<&'a str, Option<&'a str>>>,
+    resolved: Vec<ResolvedOperator<'a>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a, 'b, 'c> OperatorResolver<'a, 'b, 'c> {
+    fn resolve_methods<P>(&mut self, this_type: Option<&'a str>, methods: &'b MethodList<'a, P>) {
+        for (method, _) in get_methods(methods, false) {
+            if let Some(body) = &method.body {
+                self.resolve_body(this_type, &method.args, body);
+            }
+        }
+    }
+
+    fn resolve_body(
+        &mut self,
+        this_type: Option<&'a str>,
+        args: &'b [Documented<'a, AstMethodArgument<'a>>],
+        body: &'b ExpressionBlockAstNode<'a>,
+    ) {
+        self.this_type = this_type;
+
+        let mut arg_scope = HashMap::new();
+        for arg in args {
+            if let AstMethodArgument::Regular(_, name, arg_type) = &arg.node {
+                arg_scope.insert(*name, self.get_known_type(arg_type));
+            }
+        }
+
+        self.scopes = vec![arg_scope];
+        self.resolve_block(body);
+        self.scopes.clear();
+    }
+
+    fn resolve_block(&mut self, block: &'b ExpressionBlockAstNode<'a>) -> Option<&'a str> {
+        self.scopes.push(HashMap::new());
+
+        for statement in &block.statements {
+            match statement {
+                AstStatement::Expression(_, expression) => {
+                    self.resolve_expression(expression);
+                }
+                AstStatement::VariableDec(_, variable_dec) => {
+                    let value_type = variable_dec
+                        .value
+                        .as_ref()
+                        .and_then(|value| self.resolve_expression(value));
+
+                    let var_type = match &variable_dec.var_type {
+                        Some(var_type) => self.get_known_type(var_type),
+                        None => value_type,
+                    };
+
+                    match &variable_dec.pattern {
+                        AstBindingPattern::Name(_, name)
+                        | AstBindingPattern::MutableName(_, name) => self.bind(name, var_type),
+                        pattern => self.bind_binding_pattern(pattern),
+                    }
+                }
+                AstStatement::Assignment(_, target, value) => {
+                    self.resolve_expression(target);
+                    self.resolve_expression(value);
+                }
+            }
+        }
+
+        let value_type = block
+            .value
+            .as_ref()
+            .and_then(|value| self.resolve_expression(value));
+
+        self.scopes.pop();
+
+        value_type
+    }
+
+    /// Resolves every operator in an expression, returning the expression's type
+    /// if it is known to be one of the collected types.
+    fn resolve_expression(&mut self, expression: &'b AstExpression<'a>) -> Option<&'a str> {
+        match expression {
+            AstExpression::Literal(_, _)
+            | AstExpression::BigThis(_)
+            | AstExpression::Break(_, _)
+            | AstExpression::Continue(_, _) => None,
+
+            AstExpression::Name(_, name) => self.lookup(name),
+            AstExpression::This(_) => self.this_type,
+
+            AstExpression::Parenthesized(_, inner) => self.resolve_expression(inner),
+            AstExpression::Cast(_, inner, cast_type) => {
+                self.resolve_expression(inner);
+                self.get_known_type(cast_type)
+            }
+            AstExpression::MemberAccess(_, inner, _) => {
+                self.resolve_expression(inner);
+                None
+            }
+
+            AstExpression::Return(_, value) | AstExpression::Yield(_, value) => {
+                if let Some(value) = value {
+                    self.resolve_expression(value);
+                }
+                None
+            }
+
+            AstExpression::Tuple(_, elements) | AstExpression::Array(_, elements) => {
+                for element in elements {
+                    self.resolve_expression(element);
+                }
+                None
+            }
+
+            AstExpression::Block(_, block) => self.resolve_block(block),
+
+            AstExpression::If(_, if_node) => {
+                self.resolve_expression(&if_node.condition);
+                self.resolve_block(&if_node.then_block);
+
+                if let Some(else_branch) = &if_node.else_branch {
+                    self.resolve_expression(else_branch);
+                }
+                None
+            }
+            AstExpression::While(_, while_node) => {
+                self.resolve_expression(&while_node.condition);
+                self.resolve_block(&while_node.body);
+                None
+            }
+            AstExpression::Loop(_, loop_node) => {
+                self.resolve_block(&loop_node.body);
+                None
+            }
+            AstExpression::For(_, for_node) => {
+                self.resolve_expression(&for_node.iterable);
+
+                self.scopes.push(HashMap::new());
+                self.bind_binding_pattern(&for_node.pattern);
+                self.resolve_block(&for_node.body);
+                self.scopes.pop();
+                None
+            }
+            AstExpression::Match(_, match_node) => {
+                self.resolve_expression(&match_node.scrutinee);
+
+                for arm in &match_node.arms {
+                    self.scopes.push(HashMap::new());
+                    self.bind_pattern(&arm.pattern);
+
+                    if let Some(guard) = &arm.guard {
+                        self.resolve_expression(guard);
+                    }
+                    self.resolve_expression(&arm.body);
+
+                    self.scopes.pop();
+                }
+                None
+            }
+
+            AstExpression::Closure(_, closure) => {
+                let mut param_scope = HashMap::new();
+                for param in &closure.params {
+                    let param_type = param
+                        .param_type
+                        .as_ref()
+                        .and_then(|param_type| self.get_known_type(param_type));
+                    param_scope.insert(param.name, param_type);
+                }
+
+                self.scopes.push(param_scope);
+                match &closure.body {
+                    AstClosureBody::Block(body) => self.resolve_block(body),
+                    AstClosureBody::Expression(body) => self.resolve_expression(body),
+                };
+                self.scopes.pop();
+                None
+            }
+
+            AstExpression::Unary(span, operator, operand) => {
+                let operand_type = self.resolve_expression(operand);
+
+                match operator {
+                    AstUnaryOperator::Propagate => None,
+                    _ => self.resolve_operator(
+                        span,
+                        AstOverloadableOperator::Unary(*operator),
+                        operand_type,
+                    ),
+                }
+            }
+            AstExpression::Binary(span, operator, left, right) => {
+                let left_type = self.resolve_expression(left);
+                self.resolve_expression(right);
+
+                match operator {
+                    AstBinaryOperator::Or | AstBinaryOperator::And => None,
+                    _ => self.resolve_operator(
+                        span,
+                        AstOverloadableOperator::Binary(*operator),
+                        left_type,
+                    ),
+                }
+            }
+            AstExpression::Subscript(span, target, index) => {
+                let target_type = self.resolve_expression(target);
+                self.resolve_expression(index);
+
+                self.resolve_operator(span, AstOverloadableOperator::Subscript, target_type)
+            }
+            // Macros expand to code that isn't known yet.
+            AstExpression::MacroCall(_, _, args) => {
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+                None
+            }
+            AstExpression::Call(span, callee, args) => {
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+
+                match &**callee {
+                    // Calling a type (that isn't shadowed by a variable) constructs it.
+                    AstExpression::Name(_, name)
+                        if !self.is_variable(name) && self.overloads.types.contains_key(name) =>
+                    {
+                        Some(*name)
+                    }
+                    callee => {
+                        let callee_type = self.resolve_expression(callee);
+
+                        if let Some((type_name, overload)) = callee_type.and_then(|callee_type| {
+                            self.overloads
+                                .get(callee_type, AstOverloadableOperator::Call)
+                        }) {
+                            if overload.arg_count != args.len() {
+                                self.diagnostics.push(
+                                    Diagnostic::new(
+                                        format!(
+                                            "`{}`'s `operator()` takes {} argument(s), but {} were given",
+                                            type_name,
+                                            overload.arg_count,
+                                            args.len()
+                                        ),
+                                        span.clone(),
+                                    )
+                                    .with_note(
+                                        overload.name.span.clone(),
+                                        "`operator()` is declared here".to_string(),
+                                    ),
+                                );
+                            }
+                        }
+
+                        self.resolve_operator(span, AstOverloadableOperator::Call, callee_type)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves a single operator whose operand has the type `operand_type`,
+    /// returning the type that the overload returns (if it is known).
+    fn resolve_operator(
+        &mut self,
+        span: &Span,
+        operator: AstOverloadableOperator,
+        operand_type: Option<&'a str>,
+    ) -> Option<&'a str> {
+        let (type_name, overload) = self.overloads.get(operand_type?, operator)?;
+
+        self.resolved.push(ResolvedOperator {
+            span: span.clone(),
+            operator,
+            type_name,
+        });
+
+        // `This` refers to the type that declares the overload.
+        match overload.return_type.and_then(AstType::get_name) {
+            Some("This") => Some(type_name),
+            Some(name) if self.overloads.types.contains_key(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of a type if it is one of the collected types (or `This`,
+    /// when inside of one).
+    fn get_known_type(&self, ast_type: &AstType<'a>) -> Option<&'a str> {
+        match ast_type {
+            AstType::RootName(_, "This") => self.this_type,
+            AstType::RootName(_, _) | AstType::GenericOf(_, _, _) => ast_type
+                .get_name()
+                .filter(|name| self.overloads.types.contains_key(name)),
+            _ => None,
+        }
+    }
+
+    fn bind(&mut self, name: &'a str, var_type: Option<&'a str>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, var_type);
+        }
+    }
+
+    /// Binds every name in a binding pattern to an unknown type.
+    fn bind_binding_pattern(&mut self, pattern: &AstBindingPattern<'a>) {
+        match pattern {
+            AstBindingPattern::Name(_, name) | AstBindingPattern::MutableName(_, name) => {
+                self.bind(name, None)
+            }
+            AstBindingPattern::Ignored(_) => (),
+            AstBindingPattern::Tuple(_, elements) => elements
+                .iter()
+                .for_each(|element| self.bind_binding_pattern(element)),
+        }
+    }
+
+    /// Binds every name in a match pattern to an unknown type.
+    fn bind_pattern(&mut self, pattern: &AstPattern<'a>) {
+        match pattern {
+            AstPattern::Name(_, name) | AstPattern::MutableName(_, name) => self.bind(name, None),
+            AstPattern::Case(_, _, elements)
+            | AstPattern::Tuple(_, elements)
+            | AstPattern::Or(_, elements) => elements
+                .iter()
+                .for_each(|element| self.bind_pattern(element)),
+            _ => (),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&'a str> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .flatten()
+    }
+
+    fn is_variable(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name))
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::{HashMap, HashSet};
+
+use logos::Span;
+
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::super::parser::proper_parser::ast::types::{AstType, GenericArgs, TypeAliasAstNode};
+use super::diagnostic::Diagnostic;
+use super::walk::walk_file_types;
+
+/// Maps the names of generic parameters to the types they stand for.
+type Substitutions<'a> = HashMap<&'a str, AstType<'a>>;
+
+/// This struct holds every type alias declared at the top level of a file, so
+/// that they can be expanded wherever they are used.
+///
+/// Collecting the aliases rejects any that expand to themselves, so expanding is
+/// guaranteed to finish.
+pub struct TypeAliases<'a, 'b> {
+    aliases: HashMap<&'a str, &'b TypeAliasAstNode<'a>>,
+}
+
+impl<'a, 'b> TypeAliases<'a, 'b> {
+    /// Collects the type aliases (exported or not) from the top-level nodes of a
+    /// file, failing if any are declared twice or form a cycle.
+    pub fn collect(nodes: &'b [TopLevelAstNode<'a>]) -> Result<Self, Vec<Diagnostic>> {
+        let mut aliases: HashMap<&'a str, &'b TypeAliasAstNode<'a>> = HashMap::new();
+        let mut diagnostics = Vec::new();
+
+        for node in nodes {
+            if let TopLevelAstNode::TypeAlias(_, alias) = node.get_declaration() {
+                let name = alias.aliased_type.name;
+
+                match aliases.get(name) {
+                    Some(first_alias) => diagnostics.push(
+                        Diagnostic::new(
+                            format!("type alias `{}` is declared more than once", name),
+                            alias.aliased_type.span.clone(),
+                        )
+                        .with_note(
+                            first_alias.aliased_type.span.clone(),
+                            format!("`{}` is first declared here", name),
+                        ),
+                    ),
+                    None => {
+                        aliases.insert(name, alias);
+                    }
+                }
+            }
+        }
+
+        let type_aliases = TypeAliases { aliases };
+
+        diagnostics.append(&mut type_aliases.find_cycles());
+
+        if diagnostics.is_empty() {
+            Ok(type_aliases)
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Expands every alias in `ast_type`, substituting the generic arguments that
+    /// each one is used with into its definition. `generic_params` are the names
+    /// of the generic parameters in scope where `ast_type` is written, which
+    /// shadow any aliases with the same name.
+    ///
+    /// (The spans inside of an expanded alias point at its declaration.)
+    pub fn expand(
+        &self,
+        ast_type: &AstType<'a>,
+        generic_params: &[&'a str],
+    ) -> Result<AstType<'a>, Diagnostic> {
+        let substitutions = generic_params
+            .iter()
+            .map(|param| (*param, AstType::RootName(ast_type.get_span(), param)))
+            .collect();
+
+        self.expand_with(ast_type, &substitutions)
+    }
+
+    /// Expands every type written outside of the alias declarations (like field
+    /// types, arguments, and casts), to make sure that the aliases they use are
+    /// given the right number of arguments.
+    pub fn check_uses(&self, nodes: &[TopLevelAstNode<'a>]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        walk_file_types(nodes, &mut |ast_type, generic_params| {
+            if let Err(diagnostic) = self.expand(ast_type, generic_params) {
+                diagnostics.push(diagnostic);
+            }
+        });
+
+        diagnostics
+    }
+
+    /// Expands the right-hand side and parameter defaults of every alias, to make
+    /// sure that the aliases they use are given the right number of arguments.
+    pub fn check_declarations(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for alias in self.sorted_aliases() {
+            // The alias's own parameters are left as they are.
+            let mut substitutions = HashMap::new();
+
+            for param in alias.aliased_type.get_generic_params() {
+                let param = &param.node;
+
+                if let Some(default) = &param.default {
+                    if let Err(diagnostic) = self.expand_with(default, &substitutions) {
+                        diagnostics.push(diagnostic);
+                    }
+                }
+
+                substitutions.insert(
+                    param.name,
+                    AstType::RootName(param.span.clone(), param.name),
+                );
+            }
+
+            if let Err(diagnostic) = self.expand_with(&alias.orig_type, &substitutions) {
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        diagnostics
+    }
+
+    fn expand_with(
+        &self,
+        ast_type: &AstType<'a>,
+        substitutions: &Substitutions<'a>,
+    ) -> Result<AstType<'a>, Diagnostic> {
+        Ok(match ast_type {
+            AstType::RootName(span, name) => {
+                match (substitutions.get(name), self.aliases.get(name)) {
+                    (Some(substituted_type), _) => substituted_type.clone(),
+                    (None, Some(alias)) => self.instantiate(alias, Vec::new(), span.clone())?,
+                    (None, None) => ast_type.clone(),
+                }
+            }
+            AstType::MemberOf(span, base, name) => AstType::MemberOf(
+                span.clone(),
+                Box::new(self.expand_with(base, substitutions)?),
+                name,
+            ),
+            AstType::GenericOf(span, base, args) => {
+                let expanded_args = self.expand_all(&args.args, substitutions)?;
+
+                match &**base {
+                    AstType::RootName(_, name) if !substitutions.contains_key(name) => {
+                        match self.aliases.get(name) {
+                            Some(alias) => self.instantiate(alias, expanded_args, span.clone())?,
+                            None => AstType::GenericOf(
+                                span.clone(),
+                                base.clone(),
+                                GenericArgs {
+                                    span: args.span.clone(),
+                                    args: expanded_args,
+                                },
+                            ),
+                        }
+                    }
+                    _ => AstType::GenericOf(
+                        span.clone(),
+                        Box::new(self.expand_with(base, substitutions)?),
+                        GenericArgs {
+                            span: args.span.clone(),
+                            args: expanded_args,
+                        },
+                    ),
+                }
+            }
+            AstType::Tuple(span, element_types) => {
+                AstType::Tuple(span.clone(), self.expand_all(element_types, substitutions)?)
+            }
+            AstType::Array(span, element_type) => AstType::Array(
+                span.clone(),
+                Box::new(self.expand_with(element_type, substitutions)?),
+            ),
+            AstType::Function(span, arg_types, return_type) => AstType::Function(
+                span.clone(),
+                self.expand_all(arg_types, substitutions)?,
+                match return_type {
+                    Some(return_type) => {
+                        Some(Box::new(self.expand_with(return_type, substitutions)?))
+                    }
+                    None => None,
+                },
+            ),
+            AstType::Optional(span, inner_type) => AstType::Optional(
+                span.clone(),
+                Box::new(self.expand_with(inner_type, substitutions)?),
+            ),
+        })
+    }
+
+    fn expand_all(
+        &self,
+        ast_types: &[AstType<'a>],
+        substitutions: &Substitutions<'a>,
+    ) -> Result<Vec<AstType<'a>>, Diagnostic> {
+        ast_types
+            .iter()
+            .map(|ast_type| self.expand_with(ast_type, substitutions))
+            .collect()
+    }
+
+    /// Expands a single use of `alias` with the (already expanded) `args`, filling
+    /// in defaults for any that were left out.
+    fn instantiate(
+        &self,
+        alias: &TypeAliasAstNode<'a>,
+        args: Vec<AstType<'a>>,
+        use_span: Span,
+    ) -> Result<AstType<'a>, Diagnostic> {
+        let params = alias.aliased_type.get_generic_params();
+        let required_count = params
+            .iter()
+            .filter(|param| param.node.default.is_none())
+            .count();
+
+        if args.len() < required_count || args.len() > params.len() {
+            let expected_count = if required_count == params.len() {
+                required_count.to_string()
+            } else {
+                format!("{} to {}", required_count, params.len())
+            };
+
+            return Err(Diagnostic::new(
+                format!(
+                    "type alias `{}` takes {} generic argument(s), but {} were given",
+                    alias.aliased_type.name,
+                    expected_count,
+                    args.len()
+                ),
+                use_span,
+            )
+            .with_note(
+                alias.aliased_type.span.clone(),
+                format!("`{}` is declared here", alias.aliased_type.name),
+            ));
+        }
+
+        let mut substitutions = HashMap::new();
+        let mut args = args.into_iter();
+
+        for param in params.iter().map(|param| &param.node) {
+            let substituted_type = match (args.next(), &param.default) {
+                (Some(arg), _) => arg,
+                // Defaults can refer to the parameters before them.
+                (None, Some(default)) => self.expand_with(default, &substitutions)?,
+                (None, None) => unreachable!("The number of arguments was already checked."),
+            };
+
+            substitutions.insert(param.name, substituted_type);
+        }
+
+        self.expand_with(&alias.orig_type, &substitutions)
+    }
+
+    /// Finds every alias that (directly or through other aliases) expands to
+    /// itself. Each cycle is only reported once.
+    fn find_cycles(&self) -> Vec<Diagnostic> {
+        let mut finished = HashSet::new();
+        let mut diagnostics = Vec::new();
+
+        for alias in self.sorted_aliases() {
+            self.find_cycles_from(
+                alias.aliased_type.name,
+                &mut Vec::new(),
+                &mut finished,
+                &mut diagnostics,
+            );
+        }
+
+        diagnostics
+    }
+
+    fn find_cycles_from(
+        &self,
+        name: &'a str,
+        path: &mut Vec<&'a str>,
+        finished: &mut HashSet<&'a str>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        if finished.contains(name) {
+            return;
+        }
+
+        if let Some(cycle_start) = path.iter().position(|visited| *visited == name) {
+            let cycle = &path[cycle_start..];
+
+            let chain = cycle
+                .iter()
+                .chain(Some(&name))
+                .map(|alias_name| format!("`{}`", alias_name))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            let diagnostic = cycle[1..].iter().fold(
+                Diagnostic::new(
+                    format!("type alias `{}` expands to itself ({})", name, chain),
+                    self.aliases[name].aliased_type.span.clone(),
+                ),
+                |diagnostic, alias_name| {
+                    diagnostic.with_note(
+                        self.aliases[alias_name].aliased_type.span.clone(),
+                        format!("`{}` is declared here", alias_name),
+                    )
+                },
+            );
+
+            diagnostics.push(diagnostic);
+            return;
+        }
+
+        path.push(name);
+
+        let alias = self.aliases[name];
+        let params: Vec<&str> = alias
+            .aliased_type
+            .get_generic_params()
+            .iter()
+            .map(|param| param.node.name)
+            .collect();
+
+        let mut referenced_names = Vec::new();
+        collect_root_names(&alias.orig_type, &mut referenced_names);
+        for param in alias.aliased_type.get_generic_params() {
+            if let Some(default) = &param.node.default {
+                collect_root_names(default, &mut referenced_names);
+            }
+        }
+
+        for referenced_name in referenced_names {
+            // Parameters shadow aliases with the same name.
+            if !params.contains(&referenced_name) && self.aliases.contains_key(referenced_name) {
+                self.find_cycles_from(referenced_name, path, finished, diagnostics);
+            }
+        }
+
+        path.pop();
+        finished.insert(name);
+    }
+
+    /// Gets the aliases in the order they were declared, so that diagnostics come
+    /// out in the same order every time.
+    fn sorted_aliases(&self) -> Vec<&'b TypeAliasAstNode<'a>> {
+        let mut aliases: Vec<_> = self.aliases.values().copied().collect();
+        aliases.sort_by_key(|alias| alias.span.start);
+        aliases
+    }
+}
+
+/// Collects every name that a type refers to (not counting the last segment of
+/// a member path, which can't be an alias).
+fn collect_root_names<'a>(ast_type: &AstType<'a>, names: &mut Vec<&'a str>) {
+    match ast_type {
+        AstType::RootName(_, name) => names.push(name),
+        AstType::MemberOf(_, base, _) => collect_root_names(base, names),
+        AstType::GenericOf(_, base, args) => {
+            collect_root_names(base, names);
+            args.args
+                .iter()
+                .for_each(|arg| collect_root_names(arg, names));
+        }
+        AstType::Tuple(_, element_types) => element_types
+            .iter()
+            .for_each(|element_type| collect_root_names(element_type, names)),
+        AstType::Array(_, inner_type) | AstType::Optional(_, inner_type) => {
+            collect_root_names(inner_type, names)
+        }
+        AstType::Function(_, arg_types, return_type) => {
+            arg_types
+                .iter()
+                .chain(return_type.as_deref())
+                .for_each(|arg_type| collect_root_names(arg_type, names));
+        }
+    }
+}
@@ -0,0 +1,672 @@
+//! This module holds the functions that visit every expression (or every type)
+//! in a file, for checks that care about them no matter where they are written.
+
+use super::super::parser::proper_parser::ast::classes::AstClassExtensibility;
+use super::super::parser::proper_parser::ast::constants::AssociatedConstList;
+use super::super::parser::proper_parser::ast::documentation::Documented;
+use super::super::parser::proper_parser::ast::enums::AstEnumCasePayload;
+use super::super::parser::proper_parser::ast::expressions::{
+    AstClosureBody, AstExpression, AstStatement, ExpressionBlockAstNode,
+};
+use super::super::parser::proper_parser::ast::fields::ClassFieldAstNode;
+use super::super::parser::proper_parser::ast::methods::{
+    AstMethodArgument, MethodList, MethodOrConstraintAstNode,
+};
+use super::super::parser::proper_parser::ast::patterns::{AstBindingPattern, AstPattern};
+use super::super::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use super::super::parser::proper_parser::ast::types::{
+    AstType, GenericParam, NameAndGenericsAstNode, WhereClauseAstNode,
+};
+
+/// The function that gets called on every expression that is walked over.
+pub type Visitor<'a, 'b, 'c> = dyn FnMut(&'b AstExpression<'a>) + 'c;
+
+/// The function that gets called on every expression that is walked over, along
+/// with the local variables that are in scope where it is written.
+pub type ScopedVisitor<'a, 'b, 'c> = dyn FnMut(&'b AstExpression<'a>, &LocalNames<'a>) + 'c;
+
+/// This struct holds the names of the local variables (including arguments and
+/// closure parameters) that are in scope at some point in a body, so that a name
+/// that refers to one of them can be told apart from one that refers to a
+/// top-level declaration.
+#[derive(Default)]
+pub struct LocalNames<'a> {
+    scopes: Vec<Vec<&'a str>>,
+}
+
+impl<'a> LocalNames<'a> {
+    pub fn contains(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(&name))
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: &'a str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(name);
+        }
+    }
+
+    fn bind_args(&mut self, args: &[Documented<'a, AstMethodArgument<'a>>]) {
+        for arg in args {
+            if let AstMethodArgument::Regular(_, name, _) = &arg.node {
+                self.bind(name);
+            }
+        }
+    }
+
+    fn bind_binding_pattern(&mut self, pattern: &AstBindingPattern<'a>) {
+        match pattern {
+            AstBindingPattern::Name(_, name) | AstBindingPattern::MutableName(_, name) => {
+                self.bind(name)
+            }
+            AstBindingPattern::Ignored(_) => (),
+            AstBindingPattern::Tuple(_, elements) => elements
+                .iter()
+                .for_each(|element| self.bind_binding_pattern(element)),
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &AstPattern<'a>) {
+        match pattern {
+            AstPattern::Name(_, name) | AstPattern::MutableName(_, name) => self.bind(name),
+            AstPattern::Case(_, _, elements)
+            | AstPattern::Tuple(_, elements)
+            | AstPattern::Or(_, elements) => elements
+                .iter()
+                .for_each(|element| self.bind_pattern(element)),
+            _ => (),
+        }
+    }
+}
+
+/// Calls `visit` on every expression in the top-level nodes of a file (including
+/// ones nested inside of other expressions), visiting parents before their
+/// children.
+pub fn walk_file<'a, 'b>(nodes: &'b [TopLevelAstNode<'a>], visit: &mut Visitor<'a, 'b, '_>) {
+    walk_file_in_scope(nodes, &mut |expression, _| visit(expression));
+}
+
+/// Like `walk_file`, but also gives `visit` the local variables that are in
+/// scope where each expression is written.
+pub fn walk_file_in_scope<'a, 'b>(
+    nodes: &'b [TopLevelAstNode<'a>],
+    visit: &mut ScopedVisitor<'a, 'b, '_>,
+) {
+    let locals = &mut LocalNames::default();
+
+    for node in nodes {
+        match node.get_declaration() {
+            TopLevelAstNode::EnumDec(_, enum_dec) => {
+                for case in &enum_dec.cases {
+                    if let Some(discriminant) = &case.node.discriminant {
+                        walk_expression(discriminant, locals, visit);
+                    }
+
+                    if let AstEnumCasePayload::Struct(_, fields) = &case.node.payload {
+                        for default in fields
+                            .iter()
+                            .filter_map(|field| field.node.default.as_ref())
+                        {
+                            walk_expression(default, locals, visit);
+                        }
+                    }
+                }
+
+                for associated_const in &enum_dec.consts {
+                    walk_expression(&associated_const.node.value, locals, visit);
+                }
+
+                walk_methods(&enum_dec.methods, locals, visit);
+            }
+            TopLevelAstNode::StructDec(_, struct_dec) => {
+                for default in struct_dec
+                    .fields
+                    .iter()
+                    .filter_map(|field| field.node.default.as_ref())
+                {
+                    walk_expression(default, locals, visit);
+                }
+
+                for associated_const in &struct_dec.consts {
+                    walk_expression(&associated_const.node.value, locals, visit);
+                }
+
+                walk_methods(&struct_dec.methods, locals, visit);
+            }
+            TopLevelAstNode::ClassDec(_, class_dec) => {
+                for field in &class_dec.fields {
+                    if let ClassFieldAstNode::Declared(_, field) = &field.node {
+                        if let Some(default) = &field.default {
+                            walk_expression(default, locals, visit);
+                        }
+                    }
+                }
+
+                for static_field in &class_dec.statics {
+                    walk_expression(&static_field.node.value, locals, visit);
+                }
+
+                for associated_const in &class_dec.consts {
+                    walk_expression(&associated_const.node.value, locals, visit);
+                }
+
+                for constructor in &class_dec.constructors {
+                    locals.push_scope();
+                    locals.bind_args(&constructor.node.args);
+
+                    for super_call in &constructor.node.super_calls {
+                        for arg in &super_call.args {
+                            walk_expression(arg, locals, visit);
+                        }
+                    }
+
+                    walk_block(&constructor.node.body, locals, visit);
+                    locals.pop_scope();
+                }
+
+                walk_methods(&class_dec.methods, locals, visit);
+            }
+            TopLevelAstNode::InterfaceDec(_, interface_dec) => {
+                walk_methods(&interface_dec.methods, locals, visit)
+            }
+            TopLevelAstNode::FunctionDec(_, function_dec) => {
+                walk_body(&function_dec.args, &function_dec.body, locals, visit)
+            }
+            TopLevelAstNode::ConstDec(_, const_dec) => {
+                walk_expression(&const_dec.value, locals, visit)
+            }
+            _ => (),
+        }
+    }
+}
+
+fn walk_methods<'a, 'b, P>(
+    methods: &'b MethodList<'a, P>,
+    locals: &mut LocalNames<'a>,
+    visit: &mut ScopedVisitor<'a, 'b, '_>,
+) {
+    for method in methods {
+        match method {
+            MethodOrConstraintAstNode::Method(_, method) => {
+                if let Some(body) = &method.node.body {
+                    walk_body(&method.node.args, body, locals, visit);
+                }
+            }
+            MethodOrConstraintAstNode::Constraint(_, _, constrained_methods) => {
+                walk_methods(constrained_methods, locals, visit)
+            }
+        }
+    }
+}
+
+fn walk_body<'a, 'b>(
+    args: &[Documented<'a, AstMethodArgument<'a>>],
+    body: &'b ExpressionBlockAstNode<'a>,
+    locals: &mut LocalNames<'a>,
+    visit: &mut ScopedVisitor<'a, 'b, '_>,
+) {
+    locals.push_scope();
+    locals.bind_args(args);
+    walk_block(body, locals, visit);
+    locals.pop_scope();
+}
+
+fn walk_block<'a, 'b>(
+    block: &'b ExpressionBlockAstNode<'a>,
+    locals: &mut LocalNames<'a>,
+    visit: &mut ScopedVisitor<'a, 'b, '_>,
+) {
+    locals.push_scope();
+
+    for statement in &block.statements {
+        match statement {
+            AstStatement::Expression(_, expression) => walk_expression(expression, locals, visit),
+            AstStatement::VariableDec(_, variable_dec) => {
+                if let Some(value) = &variable_dec.value {
+                    walk_expression(value, locals, visit);
+                }
+                locals.bind_binding_pattern(&variable_dec.pattern);
+            }
+            AstStatement::Assignment(_, target, value) => {
+                walk_expression(target, locals, visit);
+                walk_expression(value, locals, visit);
+            }
+        }
+    }
+
+    if let Some(value) = &block.value {
+        walk_expression(value, locals, visit);
+    }
+
+    locals.pop_scope();
+}
+
+fn walk_expression<'a, 'b>(
+    expression: &'b AstExpression<'a>,
+    locals: &mut LocalNames<'a>,
+    visit: &mut ScopedVisitor<'a, 'b, '_>,
+) {
+    visit(expression, locals);
+
+    match expression {
+        AstExpression::Literal(_, _)
+        | AstExpression::Name(_, _)
+        | AstExpression::This(_)
+        | AstExpression::BigThis(_)
+        | AstExpression::Break(_, _)
+        | AstExpression::Continue(_, _) => (),
+
+        AstExpression::Parenthesized(_, inner)
+        | AstExpression::Unary(_, _, inner)
+        | AstExpression::Cast(_, inner, _)
+        | AstExpression::MemberAccess(_, inner, _) => walk_expression(inner, locals, visit),
+
+        AstExpression::Return(_, value) | AstExpression::Yield(_, value) => {
+            if let Some(value) = value {
+                walk_expression(value, locals, visit);
+            }
+        }
+
+        AstExpression::Tuple(_, elements) | AstExpression::Array(_, elements) => {
+            for element in elements {
+                walk_expression(element, locals, visit);
+            }
+        }
+
+        AstExpression::Block(_, block) => walk_block(block, locals, visit),
+
+        AstExpression::If(_, if_node) => {
+            walk_expression(&if_node.condition, locals, visit);
+            walk_block(&if_node.then_block, locals, visit);
+
+            if let Some(else_branch) = &if_node.else_branch {
+                walk_expression(else_branch, locals, visit);
+            }
+        }
+        AstExpression::While(_, while_node) => {
+            walk_expression(&while_node.condition, locals, visit);
+            walk_block(&while_node.body, locals, visit);
+        }
+        AstExpression::Loop(_, loop_node) => walk_block(&loop_node.body, locals, visit),
+        AstExpression::For(_, for_node) => {
+            walk_expression(&for_node.iterable, locals, visit);
+
+            locals.push_scope();
+            locals.bind_binding_pattern(&for_node.pattern);
+            walk_block(&for_node.body, locals, visit);
+            locals.pop_scope();
+        }
+        AstExpression::Match(_, match_node) => {
+            walk_expression(&match_node.scrutinee, locals, visit);
+
+            for arm in &match_node.arms {
+                locals.push_scope();
+                locals.bind_pattern(&arm.pattern);
+
+                if let Some(guard) = &arm.guard {
+                    walk_expression(guard, locals, visit);
+                }
+
+                walk_expression(&arm.body, locals, visit);
+                locals.pop_scope();
+            }
+        }
+
+        AstExpression::Closure(_, closure) => {
+            locals.push_scope();
+            for param in &closure.params {
+                locals.bind(param.name);
+            }
+
+            match &closure.body {
+                AstClosureBody::Block(body) => walk_block(body, locals, visit),
+                AstClosureBody::Expression(body) => walk_expression(body, locals, visit),
+            }
+            locals.pop_scope();
+        }
+
+        AstExpression::Binary(_, _, left, right) | AstExpression::Subscript(_, left, right) => {
+            walk_expression(left, locals, visit);
+            walk_expression(right, locals, visit);
+        }
+
+        AstExpression::Call(_, callee, args) => {
+            walk_expression(callee, locals, visit);
+
+            for arg in args {
+                walk_expression(arg, locals, visit);
+            }
+        }
+        AstExpression::MacroCall(_, _, args) => {
+            for arg in args {
+                walk_expression(arg, locals, visit);
+            }
+        }
+    }
+}
+
+/// The function that gets called on every type that is walked over, along with
+/// the names of the generic parameters that are in scope where it is written.
+pub type TypeVisitor<'a, 'b, 'c> = dyn FnMut(&'b AstType<'a>, &[&'a str]) + 'c;
+
+/// Calls `visit` on every type written in the top-level nodes of a file, from
+/// field types and supertypes down to casts and `let` annotations inside of
+/// bodies. (Types nested inside of other types aren't visited on their own.)
+///
+/// Type alias declarations are skipped, since `TypeAliases` checks them itself.
+pub fn walk_file_types<'a, 'b>(
+    nodes: &'b [TopLevelAstNode<'a>],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for node in nodes {
+        match node.get_declaration() {
+            TopLevelAstNode::EnumDec(_, enum_dec) => {
+                let params = walk_declared_generics(&enum_dec.enum_type, &[], visit);
+
+                walk_type_list(&enum_dec.implements, &params, visit);
+                walk_where_clause_types(&enum_dec.where_clause, &params, visit);
+
+                for case in &enum_dec.cases {
+                    match &case.node.payload {
+                        AstEnumCasePayload::Unit => (),
+                        AstEnumCasePayload::Tuple(_, element_types) => {
+                            walk_type_list(element_types, &params, visit)
+                        }
+                        AstEnumCasePayload::Struct(_, fields) => {
+                            for field in fields {
+                                visit(&field.node.field_type, &params);
+
+                                if let Some(default) = &field.node.default {
+                                    walk_expression_types(default, &params, visit);
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(discriminant) = &case.node.discriminant {
+                        walk_expression_types(discriminant, &params, visit);
+                    }
+                }
+
+                walk_const_types(&enum_dec.consts, &params, visit);
+                walk_method_types(&enum_dec.methods, &params, visit);
+            }
+            TopLevelAstNode::StructDec(_, struct_dec) => {
+                let params = walk_declared_generics(&struct_dec.struct_type, &[], visit);
+
+                walk_type_list(&struct_dec.implements, &params, visit);
+                walk_where_clause_types(&struct_dec.where_clause, &params, visit);
+
+                for field in &struct_dec.fields {
+                    visit(&field.node.field_type, &params);
+
+                    if let Some(default) = &field.node.default {
+                        walk_expression_types(default, &params, visit);
+                    }
+                }
+
+                walk_const_types(&struct_dec.consts, &params, visit);
+                walk_method_types(&struct_dec.methods, &params, visit);
+            }
+            TopLevelAstNode::ClassDec(_, class_dec) => {
+                let params = walk_declared_generics(&class_dec.class_type, &[], visit);
+
+                if let AstClassExtensibility::Sealed(_, permitted) = &class_dec.extensibility {
+                    walk_type_list(permitted, &params, visit);
+                }
+                walk_type_list(&class_dec.extends, &params, visit);
+                walk_type_list(&class_dec.implements, &params, visit);
+                walk_where_clause_types(&class_dec.where_clause, &params, visit);
+
+                for field in &class_dec.fields {
+                    if let ClassFieldAstNode::Declared(_, field) = &field.node {
+                        visit(&field.field_type, &params);
+
+                        if let Some(default) = &field.default {
+                            walk_expression_types(default, &params, visit);
+                        }
+                    }
+                }
+
+                for static_field in &class_dec.statics {
+                    visit(&static_field.node.field_type, &params);
+                    walk_expression_types(&static_field.node.value, &params, visit);
+                }
+
+                walk_const_types(&class_dec.consts, &params, visit);
+
+                for constructor in &class_dec.constructors {
+                    walk_arg_types(&constructor.node.args, &params, visit);
+
+                    for super_call in &constructor.node.super_calls {
+                        if let Some(parent) = &super_call.parent {
+                            visit(parent, &params);
+                        }
+
+                        for arg in &super_call.args {
+                            walk_expression_types(arg, &params, visit);
+                        }
+                    }
+
+                    walk_block_types(&constructor.node.body, &params, visit);
+                }
+
+                walk_method_types(&class_dec.methods, &params, visit);
+            }
+            TopLevelAstNode::InterfaceDec(_, interface_dec) => {
+                let params = walk_declared_generics(&interface_dec.interface_type, &[], visit);
+
+                walk_type_list(&interface_dec.extends, &params, visit);
+                walk_where_clause_types(&interface_dec.where_clause, &params, visit);
+                walk_method_types(&interface_dec.methods, &params, visit);
+            }
+            TopLevelAstNode::FunctionDec(_, function_dec) => {
+                let params = walk_declared_generics(&function_dec.function_type, &[], visit);
+
+                walk_arg_types(&function_dec.args, &params, visit);
+                if let Some(return_type) = &function_dec.return_type {
+                    visit(return_type, &params);
+                }
+                walk_where_clause_types(&function_dec.where_clause, &params, visit);
+                walk_block_types(&function_dec.body, &params, visit);
+            }
+            TopLevelAstNode::ConstDec(_, const_dec) => {
+                if let Some(const_type) = &const_dec.const_type {
+                    visit(const_type, &[]);
+                }
+                walk_expression_types(&const_dec.value, &[], visit);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Visits the bounds and defaults of a declaration's generic parameters,
+/// returning every generic parameter that is in scope inside of it.
+fn walk_declared_generics<'a, 'b>(
+    name_and_generics: &'b NameAndGenericsAstNode<'a>,
+    outer_params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) -> Vec<&'a str> {
+    walk_generic_param_types(name_and_generics.get_generic_params(), outer_params, visit)
+}
+
+fn walk_generic_param_types<'a, 'b>(
+    generic_params: &'b [Documented<'a, GenericParam<'a>>],
+    outer_params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) -> Vec<&'a str> {
+    let params: Vec<&'a str> = outer_params
+        .iter()
+        .copied()
+        .chain(generic_params.iter().map(|param| param.node.name))
+        .collect();
+
+    for param in generic_params {
+        walk_type_list(&param.node.bounds, &params, visit);
+
+        if let Some(default) = &param.node.default {
+            visit(default, &params);
+        }
+    }
+
+    params
+}
+
+fn walk_where_clause_types<'a, 'b>(
+    where_clause: &'b Option<WhereClauseAstNode<'a>>,
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for predicate in where_clause.iter().flat_map(|clause| &clause.predicates) {
+        visit(&predicate.bounded_type, params);
+        walk_type_list(&predicate.bounds, params, visit);
+    }
+}
+
+fn walk_type_list<'a, 'b>(
+    ast_types: &'b [AstType<'a>],
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for ast_type in ast_types {
+        visit(ast_type, params);
+    }
+}
+
+fn walk_arg_types<'a, 'b>(
+    args: &'b [Documented<'a, AstMethodArgument<'a>>],
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for arg in args {
+        if let AstMethodArgument::Regular(_, _, arg_type) = &arg.node {
+            visit(arg_type, params);
+        }
+    }
+}
+
+fn walk_const_types<'a, 'b, P>(
+    consts: &'b AssociatedConstList<'a, P>,
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for associated_const in consts {
+        visit(&associated_const.node.const_type, params);
+        walk_expression_types(&associated_const.node.value, params, visit);
+    }
+}
+
+fn walk_method_types<'a, 'b, P>(
+    methods: &'b MethodList<'a, P>,
+    outer_params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for method in methods {
+        match method {
+            MethodOrConstraintAstNode::Method(_, method) => {
+                let method = &method.node;
+                let params = walk_declared_generics(&method.new_type, outer_params, visit);
+
+                walk_arg_types(&method.args, &params, visit);
+                if let Some(return_type) = &method.return_type {
+                    visit(return_type, &params);
+                }
+                walk_where_clause_types(&method.where_clause, &params, visit);
+
+                if let Some(body) = &method.body {
+                    walk_block_types(body, &params, visit);
+                }
+            }
+            MethodOrConstraintAstNode::Constraint(_, generic_params, constrained_methods) => {
+                let params = walk_generic_param_types(&generic_params.params, outer_params, visit);
+
+                walk_method_types(constrained_methods, &params, visit);
+            }
+        }
+    }
+}
+
+fn walk_block_types<'a, 'b>(
+    block: &'b ExpressionBlockAstNode<'a>,
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    walk_statement_types(block, params, visit);
+    walk_block(block, &mut LocalNames::default(), &mut |expression, _| {
+        walk_own_types(expression, params, visit)
+    });
+}
+
+fn walk_expression_types<'a, 'b>(
+    expression: &'b AstExpression<'a>,
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    walk_expression(
+        expression,
+        &mut LocalNames::default(),
+        &mut |expression, _| walk_own_types(expression, params, visit),
+    );
+}
+
+/// Visits the types written directly in an expression (but not in the
+/// expressions inside of it, which `walk_expression` gets to on its own).
+fn walk_own_types<'a, 'b>(
+    expression: &'b AstExpression<'a>,
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    match expression {
+        AstExpression::Cast(_, _, cast_type) => visit(cast_type, params),
+        AstExpression::Closure(_, closure) => {
+            for param_type in closure
+                .params
+                .iter()
+                .filter_map(|param| param.param_type.as_ref())
+            {
+                visit(param_type, params);
+            }
+
+            if let Some(return_type) = &closure.return_type {
+                visit(return_type, params);
+            }
+
+            if let AstClosureBody::Block(body) = &closure.body {
+                walk_statement_types(body, params, visit);
+            }
+        }
+
+        AstExpression::Block(_, block) => walk_statement_types(block, params, visit),
+        AstExpression::If(_, if_node) => walk_statement_types(&if_node.then_block, params, visit),
+        AstExpression::While(_, while_node) => {
+            walk_statement_types(&while_node.body, params, visit)
+        }
+        AstExpression::Loop(_, loop_node) => walk_statement_types(&loop_node.body, params, visit),
+        AstExpression::For(_, for_node) => walk_statement_types(&for_node.body, params, visit),
+
+        _ => (),
+    }
+}
+
+/// Visits the types of the variables declared directly in a block.
+fn walk_statement_types<'a, 'b>(
+    block: &'b ExpressionBlockAstNode<'a>,
+    params: &[&'a str],
+    visit: &mut TypeVisitor<'a, 'b, '_>,
+) {
+    for statement in &block.statements {
+        if let AstStatement::VariableDec(_, variable_dec) = statement {
+            if let Some(var_type) = &variable_dec.var_type {
+                visit(var_type, params);
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ pub mod proper_parser;
 
 use proper_parser::TopLevelAstNodeListResult;
 
-pub fn parse_str(str_to_parse: &str) -> TopLevelAstNodeListResult {
+pub fn parse_str(str_to_parse: &str) -> TopLevelAstNodeListResult<'_> {
     // Set up lexer
     let mut lxr = lexer::get_custom_lexer_from_string(str_to_parse);
 
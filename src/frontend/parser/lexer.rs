@@ -5,6 +5,6 @@ use self::{custom_lexer_struct::CustomLexerStruct, logos_lexer::LexerToken};
 
 use logos::Logos;
 
-pub fn get_custom_lexer_from_string(str_to_lex: &str) -> CustomLexerStruct<LexerToken> {
+pub fn get_custom_lexer_from_string(str_to_lex: &str) -> CustomLexerStruct<'_, LexerToken<'_>> {
     LexerToken::lexer(str_to_lex).into()
 }
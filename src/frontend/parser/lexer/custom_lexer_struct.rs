@@ -104,6 +104,34 @@ impl<'a, TokenType: Clone + core::fmt::Debug + Logos<'a>> CustomLexerStruct<'a,
     }
 }
 
+impl<'a, TokenType: Clone + core::fmt::Debug + Logos<'a>> CustomLexerStruct<'a, TokenType> {
+    /// Splits the next token in two, so that its first `first_len` bytes become
+    /// `first` and the rest become `rest`. This is for compound tokens (like `>>`)
+    /// that mean something else in certain places (like `Vec<Vec<T>>`).
+    ///
+    /// The split is permanent, so any positions saved after the split token are
+    /// invalidated. If there is no next token, this does nothing.
+    pub fn split_next_token(&mut self, first_len: usize, first: TokenType, rest: TokenType) {
+        let span = match self.peek_span() {
+            Some(span) => span,
+            None => return,
+        };
+        let split_idx = span.start + first_len;
+
+        self.tokens[self.next_token_index] = TokenWithExtras {
+            token: first,
+            span: span.start..split_idx,
+        };
+        self.tokens.insert(
+            self.next_token_index + 1,
+            TokenWithExtras {
+                token: rest,
+                span: split_idx..span.end,
+            },
+        );
+    }
+}
+
 impl<'a, TokenType: Clone + core::fmt::Debug + Logos<'a>> CustomLexerStruct<'a, TokenType> {
     pub fn save_position(&self) -> SavedLexerPosition {
         SavedLexerPosition(self.next_token_index)
@@ -24,11 +24,17 @@ pub enum LexerToken<'a> {
     Struct,
     #[regex(r"class")]
     Class,
+    #[regex(r"interface")]
+    Interface,
     #[regex(r"type")]
     Type,
 
     #[regex(r"fun")]
     Function,
+    #[regex(r"constructor")]
+    Constructor,
+    #[regex(r"operator")]
+    Operator,
 
     #[regex(r"as")]
     As,
@@ -38,6 +44,20 @@ pub enum LexerToken<'a> {
     Extends,
     #[regex(r"implements")]
     Implements,
+    #[regex(r"where")]
+    Where,
+    #[regex(r"permits")]
+    Permits,
+
+    // Class/Method modifiers
+    #[regex(r"abstract")]
+    Abstract,
+    #[regex(r"sealed")]
+    Sealed,
+    #[regex(r"final")]
+    Final,
+    #[regex(r"override")]
+    Override,
 
     // Conditional Branching
     #[regex(r"if")]
@@ -56,6 +76,9 @@ pub enum LexerToken<'a> {
     #[regex(r"loop")]
     Loop,
 
+    #[regex(r"in")]
+    In,
+
     #[regex(r"break")]
     Break,
     #[regex(r"continue")]
@@ -70,8 +93,11 @@ pub enum LexerToken<'a> {
     #[regex(r"mut")]
     Mutable,
 
+    #[regex(r"static")]
+    Static,
+
     // Publicity
-    #[regex("pub")]
+    #[regex("pub|public")]
     Public,
 
     #[regex("mpriv")]
@@ -89,6 +115,8 @@ pub enum LexerToken<'a> {
     LittleThis,
     #[regex(r"This")]
     BigThis,
+    #[regex(r"super")]
+    Super,
 
     // Evaluation
     #[regex(r"return")]
@@ -191,7 +219,13 @@ pub enum LexerToken<'a> {
     #[regex(r"!")]
     Bang,
 
-    #[regex(r"(~)|(\$)|(@)|(#)")]
+    // Attributes
+    #[regex(r"#")]
+    Hash,
+    #[regex(r"@")]
+    AtSign,
+
+    #[regex(r"(~)|(\$)")]
     Reserved,
 
     // Arrows
@@ -228,7 +262,7 @@ pub enum LexerToken<'a> {
     #[regex(r#"0x[0-9A-F]+"#, |lex| u64::from_str_radix(lex.slice().trim_start_matches("0x"), 16))]
     #[regex(r#"0b[01]+"#, |lex| u64::from_str_radix(lex.slice().trim_start_matches("0b"), 2))]
     WordLiteral(u64),
-    #[regex(r"[0-9]+(\.[0-9]*([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)", |lex| lex.slice().parse())]
+    #[regex(r"[0-9]+(\.[0-9]+([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)", |lex| lex.slice().parse())]
     FloatLiteral(f64),
 
     // Boolean literals
@@ -238,6 +272,12 @@ pub enum LexerToken<'a> {
     #[regex(r#"//.*"#)]
     #[regex(r#"/\*([^*]*\*+)((([^*/]?)|([^*/][^*]*))\*+)*/"#)]
     Comment,
+    // Documentation comments (which are kept around so that they can be attached
+    // to whatever comes after them). Like in Rust, `////` and `/***` start
+    // ordinary comments.
+    #[regex(r#"///([^/\n].*)?"#)]
+    #[regex(r#"/\*\*[^*/]([^*]|\*+[^*/])*\*+/"#)]
+    DocComment,
 
     /*
 
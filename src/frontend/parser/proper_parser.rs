@@ -1,14 +1,24 @@
 pub mod ast;
 
+pub mod attributes;
 pub mod classes;
+pub mod closures;
+pub mod constants;
+pub mod constructors;
+pub mod control_flow;
+pub mod documentation;
 pub mod enums;
 pub mod expressions;
 pub mod extends_implements;
 pub mod fields;
+pub mod functions;
 pub mod imports_exports;
+pub mod interfaces;
+pub mod members;
 pub mod methods;
 pub mod patterns;
 pub mod publicity;
+pub mod statements;
 pub mod structs;
 pub mod top_level;
 pub mod types;
@@ -2,8 +2,9 @@
 //! see used to represent a valid AST node.
 //!
 //! It hosts the modules: `top_level`, `imports_exports`, `enums`, `structs`,
-//! `classes`, `interfaces`, `fields`, `types`, `patterns`, `methods`,
-//! `publicity`, and `expressions`.
+//! `classes`, `interfaces`, `functions`, `constants`, `attributes`,
+//! `documentation`, `fields`, `types`, `patterns`, `methods`, `publicity`, and
+//! `expressions`.
 //!
 //! The only things relevant to AST data structures that aren't in this file
 //! are:
@@ -17,8 +18,11 @@ pub mod top_level {
     use logos::Span;
 
     use super::classes::ClassDecAstNode;
+    use super::constants::ConstDecAstNode;
     use super::enums::EnumDecAstNode;
-    use super::imports_exports::ImportStatementAstNode;
+    use super::functions::FunctionDecAstNode;
+    use super::imports_exports::{ExportStatementAstNode, ImportStatementAstNode};
+    use super::interfaces::InterfaceDecAstNode;
     use super::structs::StructDecAstNode;
     use super::types::TypeAliasAstNode;
 
@@ -31,32 +35,40 @@ pub mod top_level {
     /// `ExportDefault(Span, Box<TopLevelAstNode<'a>>)` (a default export
     /// statement),
     ///
+    /// `ExportItems(Span, ExportStatementAstNode<'a>)` (an export statement that
+    /// lists what it exports, possibly from another module),
+    ///
     /// `EnumDec(Span, EnumDecAstNode<'a>)` (an enum (sum type) declaration),
     ///
     /// `StructDec(Span, StructDecAstNode<'a>)` (a struct declaration),
     ///
-    /// `ClassDec(Span, ClassDecAstNode<'a>)` (a class declaration), and
+    /// `ClassDec(Span, ClassDecAstNode<'a>)` (a class declaration),
     ///
-    /// `TypeAlias(Span, TypeAliasAstNode<'a>)` (a type alias)
+    /// `FunctionDec(Span, FunctionDecAstNode<'a>)` (a free function declaration),
+    ///
+    /// `ConstDec(Span, ConstDecAstNode<'a>)` (a module-level constant),
+    ///
+    /// `InterfaceDec(Span, InterfaceDecAstNode<'a>)` (an interface declaration),
     ///
-    /// `TODO: Add Interface declarations to this.`
+    /// `TypeAlias(Span, TypeAliasAstNode<'a>)` (a type alias)
     #[derive(Debug)]
     pub enum TopLevelAstNode<'a> {
         ImportFrom(Span, ImportStatementAstNode<'a>),
 
         Export(Span, Box<TopLevelAstNode<'a>>),
         ExportDefault(Span, Box<TopLevelAstNode<'a>>),
+        ExportItems(Span, ExportStatementAstNode<'a>),
 
         EnumDec(Span, EnumDecAstNode<'a>),
 
         StructDec(Span, StructDecAstNode<'a>),
         ClassDec(Span, ClassDecAstNode<'a>),
+        InterfaceDec(Span, InterfaceDecAstNode<'a>),
 
-        TypeAlias(Span, TypeAliasAstNode<'a>),
+        FunctionDec(Span, FunctionDecAstNode<'a>),
+        ConstDec(Span, ConstDecAstNode<'a>),
 
-        /// (The span of this node is measured from the beginning of the comment to the
-        /// end of the subsequent statement.)
-        CommentedNode(Span, &'a str, Box<TopLevelAstNode<'a>>),
+        TypeAlias(Span, TypeAliasAstNode<'a>),
 
         /// This should (hopefully) never be used.
         Empty,
@@ -67,64 +79,129 @@ pub mod top_level {
         ///
         /// This gets the span of the ENTIRE statement, from the signaling keyword to
         /// the closing brackets/semicolon.
-        /// (For commented nodes, this is the span from the beginning of the comment to
-        /// the end of the contained node.)
+        /// (For declarations, this includes any documentation comments and
+        /// attributes in front of them.)
         pub fn get_span(&self) -> Span {
             match self {
                 Self::ClassDec(span, _)
-                | Self::CommentedNode(span, _, _)
+                | Self::ConstDec(span, _)
                 | Self::EnumDec(span, _)
                 | Self::Export(span, _)
                 | Self::ExportDefault(span, _)
+                | Self::ExportItems(span, _)
+                | Self::FunctionDec(span, _)
                 | Self::ImportFrom(span, _)
+                | Self::InterfaceDec(span, _)
                 | Self::StructDec(span, _)
                 | Self::TypeAlias(span, _) => span.clone(),
                 Self::Empty => unimplemented!("Can't get the span of an empty AST node."),
             }
         }
+
+        /// Gets the node underneath any exports that are wrapped around it.
+        pub fn get_declaration(&self) -> &Self {
+            match self {
+                Self::Export(_, node) | Self::ExportDefault(_, node) => node.get_declaration(),
+                _ => self,
+            }
+        }
     }
 }
 
 pub mod imports_exports {
-    //! Although this module is called `imports_exports`, it does not house
-    //! anything in relation to exports.
+    //! This module holds the `ImportStatementAstNode` and `ExportStatementAstNode`
+    //! structs, the `AstImportedItems` and `AstExportedItems` enums, and the
+    //! `AstModuleLocation` enum that both kinds of statement use.
     //!
-    //! The only things that are in this module are the `ImportStatementAstNode`
-    //! struct, and the `AstModuleLocation` enum.
-    //!
-    //! `TODO: Make this just named "imports".`
+    //! (Exports that wrap a single declaration, like `export struct ...`, are
+    //! represented by `TopLevelAstNode::Export` instead.)
 
     use logos::Span;
 
     use super::patterns::AstDestructuringPattern;
 
     /// This struct represents a top-level import statement, supporting
-    /// destructuring and rust-like module paths.
+    /// destructuring, glob imports, and importing whole modules.
     ///
     /// The fields are represented like this:
     /// ```annotated-uck
     ///                      ImportStatementAstNode.span
-    ///  _________________________________|_________________________________
-    /// │                                                                   │
-    /// import vec: { Vec, IntoIter: IntoIterCalledOnVecType } from std::vec;
-    ///        |_____________________________________________|      |______|
+    ///  ________________________________|________________________________
+    /// │                                                                 │
+    /// import vec: { Vec, IntoIter: IntoIterCalledOnVecType } from std.vec;
+    ///        |_____________________________________________|      |_____|
     ///                              │                                 │
-    ///         ImportStatementAstNode.destructuring_pattern           │
+    ///              ImportStatementAstNode.imported_items             │
     ///                                                                │
-    ///                                             ImportStatementAstNode.module_location
+    ///                                          ImportStatementAstNode.module_location
     /// ```
     #[derive(Debug)]
     pub struct ImportStatementAstNode<'a> {
         pub span: Span,
-        pub destructuring_pattern: AstDestructuringPattern<'a>,
+        pub imported_items: AstImportedItems<'a>,
         pub module_location: AstModuleLocation<'a>,
     }
 
+    /// This enum represents what an import statement brings into scope.
+    ///
+    /// `Destructured(AstDestructuringPattern<'a>)` (the items picked out by a
+    /// pattern, as in `import io: { println } from std.io;`),
+    ///
+    /// `Glob(Span)` (every exported item, as in `import * from std.io;`), and
+    ///
+    /// `Module(Span, Option<&'a str>)` (the module itself, as in `import std.io;`
+    /// or `import std.io as stdio;`, along with its alias if it has one)
+    ///
+    /// (The span of `Module` covers the module's path and alias, since they're
+    /// written together.)
+    #[derive(Debug)]
+    pub enum AstImportedItems<'a> {
+        Destructured(AstDestructuringPattern<'a>),
+        Glob(Span),
+        Module(Span, Option<&'a str>),
+    }
+
+    /// This struct represents an export statement that lists the items it
+    /// exports, rather than wrapping a declaration. If it has a
+    /// `module_location`, it re-exports items from that module.
+    ///
+    /// ```annotated-uck
+    ///                ExportStatementAstNode.span
+    ///  _____________________________|______________________________
+    /// │                                                            │
+    /// export { Vec, IntoIter: VecIntoIter } from std.collections.vec;
+    ///        |____________________________|      |__________________|
+    ///                       │                             │
+    ///      ExportStatementAstNode.exported_items          │
+    ///                                                     │
+    ///                               ExportStatementAstNode.module_location
+    /// ```
+    #[derive(Debug)]
+    pub struct ExportStatementAstNode<'a> {
+        pub span: Span,
+        pub exported_items: AstExportedItems<'a>,
+        /// This is always `Some` for `Glob` exports.
+        pub module_location: Option<AstModuleLocation<'a>>,
+    }
+
+    /// This enum represents what an export statement makes available.
+    ///
+    /// `List(Span, Vec<AstDestructuringPattern<'a>>)` (the listed items, which
+    /// can be renamed like `b: renamed`), and
+    ///
+    /// `Glob(Span)` (every exported item of another module, as in
+    /// `export * from std.io;`)
+    #[derive(Debug)]
+    pub enum AstExportedItems<'a> {
+        List(Span, Vec<AstDestructuringPattern<'a>>),
+        Glob(Span),
+    }
+
     /// This enum represents a path to a module.
     ///
     /// It parses into a tree from this:
-    /// ```
-    /// example::module::path::Type
+    /// ```text
+    /// example.module.path.Type
     /// ```
     /// into this:
     /// ```tree-representation
@@ -153,16 +230,33 @@ pub mod imports_exports {
     ///  │ │________________________│ │
     ///  │____________________________│
     /// ```
+    ///
+    /// Paths can also start relative to the current module, with `self` (which is
+    /// `CurrentModule`), `super` (which is `Super`, counting how many `super`s
+    /// are chained together, so `super.super` is 2), or `package` (the root of
+    /// the current package).
+    ///
+    /// `File(Span, &'a str)` is a string path to a `.uck` file in the same
+    /// directory (like `"utils.uck"`), which can't have anything after it.
     #[derive(Debug)]
     pub enum AstModuleLocation<'a> {
         Root(Span, &'a str),
+        CurrentModule(Span),
+        Super(Span, usize),
+        Package(Span),
+        File(Span, &'a str),
         MemberOf(Span, Box<AstModuleLocation<'a>>, &'a str),
     }
 
     impl<'a> AstModuleLocation<'a> {
         pub fn get_span(&self) -> Span {
             match self {
-                Self::Root(span, _) | Self::MemberOf(span, _, _) => span.clone(),
+                Self::Root(span, _)
+                | Self::CurrentModule(span)
+                | Self::Super(span, _)
+                | Self::Package(span)
+                | Self::File(span, _)
+                | Self::MemberOf(span, _, _) => span.clone(),
             }
         }
     }
@@ -177,29 +271,36 @@ pub mod enums {
     //! reuse is important.)
     //!
     //! Anyway, the only things housed in this module are the structs `EnumDecAstNode` and
-    //! `EnumCaseAstNode`
+    //! `EnumCaseAstNode`, along with the enums that wrap and fill in enum cases.
 
     use logos::Span;
 
+    use super::attributes::AttributeList;
+    use super::constants::AssociatedConstList;
+    use super::documentation::Documented;
+    use super::expressions::AstExpression;
+    use super::fields::FieldList;
     use super::methods::MethodList;
-    use super::publicity::AstPublicity;
-    use super::types::AstType;
+    use super::publicity::{AstPublicity, EnumCaseFieldPublicity};
+    use super::types::{AstType, NameAndGenericsAstNode, SupertypeList, WhereClauseAstNode};
 
     /// This struct represents an entire enum declaration, including the name,
-    /// generics, trait bounds, cases, and implementations.
-    /// 
+    /// generics, trait bounds, cases, associated constants, and implementations.
+    ///
     /// Here is a practical example of what each struct field corresponds to:
     /// ```text
-    ///                                enum_type                       implements
-    ///                    ________________|_______________              ___|____
-    ///                   |                                |            |        |
-    ///            │ enum EnumExample<T: Interface1 & Interface2> implements Clone {
+    ///                                   enum_type                   implements
+    ///                    ___________________|___________________   ______|_______
+    ///                   |                                       | |              |
+    ///            │ enum EnumExample<T -> Interface1 & Interface2> implements Clone {
     /// cases ==*==│===> Case1(Type, Vec<Type3>),
     ///         │  │
     ///         *==│===> Case2(Type3),
     ///         │  │
     ///         *==│===> Case3(HashMap<Type1, Type3>),
     ///            │
+    /// consts ====│===> pub const COUNT: i32 = 3;
+    ///            │
     ///          / │     pub fn interface3Method(this, ) -> EnumExample {
     /// methods │  │         // TODO: Some implementation here...
     ///         |__│     }
@@ -208,29 +309,35 @@ pub mod enums {
     #[derive(Debug)]
     pub struct EnumDecAstNode<'a> {
         pub span: Span,
-        pub enum_type: AstType<'a>,
-        pub implements: Option<AstType<'a>>,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub enum_type: NameAndGenericsAstNode<'a>,
+        pub implements: SupertypeList<'a>,
+        pub where_clause: Option<WhereClauseAstNode<'a>>,
         pub cases: CaseList<'a>,
+        pub consts: AssociatedConstList<'a, AstPublicity>,
         pub methods: MethodList<'a, AstPublicity>,
     }
 
-    /// This type alias is pretty self-explainatory, it's a list of enum cases.
+    /// This type alias is pretty self-explainatory, it's a list of enum cases
+    /// (along with their documentation comments).
     ///
     /// ```text
     ///     │ enum Enum1 {
     ///     │
     /// 0 ==│===> Case1(Type, Vec<Type3>),
     ///     │  
-    /// 1 ==│===> Case2(Type3),
+    /// 1 ==│===> Case2 { x -> Type3 },
     ///     │
-    /// 2 ==│===> Case3(HashMap<Type1, Type3>),
+    /// 2 ==│===> Case3 = 2,
     ///     │
     ///     │ }
     /// ```
-    pub type CaseList<'a> = Vec<EnumCaseAstNode<'a>>;
+    pub type CaseList<'a> = Vec<Documented<'a, EnumCaseAstNode<'a>>>;
 
-    /// This struct represents a single enum case, which is basically just a case
-    /// name and tuple of types that are contained by that case.
+    /// This struct represents a single enum case, which is a case name along with
+    /// whatever is contained by that case, and optionally an explicit
+    /// discriminant.
     ///
     /// Here is what each struct field refers to:
     /// ```text
@@ -238,100 +345,633 @@ pub mod enums {
     ///              __________|__________
     ///             │                     │
     ///             Case1(Type, Vec<Type3>),
-    ///             │___│ │______________│
+    ///             │___││________________│
     ///               │            │
     /// EnumCaseAstNode::case_name │
     ///                            │
-    ///               EnumCaseAstNode::case_args
+    ///                 EnumCaseAstNode::payload
+    ///
+    ///             Case3 = 2,
+    ///                     │
+    ///       EnumCaseAstNode::discriminant
     /// ```
     #[derive(Debug)]
     pub struct EnumCaseAstNode<'a> {
         pub span: Span,
+        pub attributes: AttributeList<'a>,
         pub case_name: &'a str,
-        pub case_args: Vec<AstType<'a>>,
+        pub payload: AstEnumCasePayload<'a>,
+        pub discriminant: Option<AstExpression<'a>>,
+    }
+
+    /// This enum represents what an enum case holds, which can be nothing, a tuple
+    /// of types, or named fields (which are as public as the enum is):
+    /// ```text
+    /// Quit
+    ///
+    /// Write(String)
+    ///      |______|
+    ///       Tuple
+    ///
+    /// Move { x -> i32, y -> i32 }
+    ///      |____________________|
+    ///              Struct
+    /// ```
+    #[derive(Debug)]
+    pub enum AstEnumCasePayload<'a> {
+        Unit,
+        Tuple(Span, Vec<AstType<'a>>),
+        Struct(Span, FieldList<'a, EnumCaseFieldPublicity>),
     }
 }
 
 pub mod structs {
     use logos::Span;
 
+    use super::attributes::AttributeList;
+    use super::constants::AssociatedConstList;
+    use super::documentation::Documented;
+    use super::expressions::{AstExpression, AstStatement, ExpressionBlockAstNode};
     use super::fields::FieldList;
-    use super::methods::MethodList;
+    use super::methods::{AstMethodArgument, ConstructorAstNode, MethodList};
     use super::publicity::AstPublicity;
-    use super::types::AstType;
+    use super::types::{NameAndGenericsAstNode, SupertypeList, WhereClauseAstNode};
 
     #[derive(Debug)]
     pub struct StructDecAstNode<'a> {
         pub span: Span,
-        pub struct_type: AstType<'a>,
-        pub implements: Option<AstType<'a>>,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub struct_type: NameAndGenericsAstNode<'a>,
+        pub implements: SupertypeList<'a>,
+        pub where_clause: Option<WhereClauseAstNode<'a>>,
         pub fields: FieldList<'a, AstPublicity>,
+        pub consts: AssociatedConstList<'a, AstPublicity>,
         pub methods: MethodList<'a, AstPublicity>,
     }
+
+    impl<'a> StructDecAstNode<'a> {
+        /// Builds the constructor that every struct gets (since structs can't
+        /// declare their own). It takes the fields that don't have defaults, in
+        /// the order they were declared, and assigns each one to the field with
+        /// the same name.
+        ///
+        /// The constructor is only as public as the least public field it takes,
+        /// and everything in it points back at the fields it was built from.
+        ///
+        /// Fields with defaults are left out of the body, since (like for every
+        /// other constructor) defaults are assigned before it runs.
+        pub fn memberwise_constructor(&self) -> ConstructorAstNode<'a, AstPublicity> {
+            let mut publicity = AstPublicity::Public;
+            let mut args = Vec::new();
+            let mut statements = Vec::new();
+
+            for field in self
+                .fields
+                .iter()
+                .filter(|field| field.node.default.is_none())
+            {
+                let span = field.node.span.clone();
+                let name = field.node.name;
+
+                publicity = match (publicity, &field.node.publicity) {
+                    (AstPublicity::Private, _) | (_, AstPublicity::Private) => {
+                        AstPublicity::Private
+                    }
+                    (AstPublicity::ModulePrivate, _) | (_, AstPublicity::ModulePrivate) => {
+                        AstPublicity::ModulePrivate
+                    }
+                    _ => AstPublicity::Public,
+                };
+
+                args.push(Documented {
+                    span: field.span.clone(),
+                    docs: field.docs.clone(),
+                    node: AstMethodArgument::Regular(
+                        span.clone(),
+                        name,
+                        field.node.field_type.clone(),
+                    ),
+                });
+
+                statements.push(AstStatement::Assignment(
+                    span.clone(),
+                    AstExpression::MemberAccess(
+                        span.clone(),
+                        Box::new(AstExpression::This(span.clone())),
+                        name,
+                    ),
+                    AstExpression::Name(span, name),
+                ));
+            }
+
+            ConstructorAstNode {
+                span: self.span.clone(),
+                attributes: Vec::new(),
+                publicity,
+                args,
+                super_calls: Vec::new(),
+                body: ExpressionBlockAstNode {
+                    span: self.span.clone(),
+                    statements,
+                    value: None,
+                },
+            }
+        }
+    }
 }
 
 pub mod classes {
     use logos::Span;
 
-    use super::fields::FieldList;
-    use super::methods::MethodList;
+    use super::attributes::AttributeList;
+    use super::constants::AssociatedConstList;
+    use super::fields::{ClassFieldList, StaticFieldList};
+    use super::methods::{ConstructorList, MethodList};
     use super::publicity::AstClassItemPublicity;
-    use super::types::AstType;
+    use super::types::{NameAndGenericsAstNode, SupertypeList, WhereClauseAstNode};
 
+    /// This struct represents a class declaration. Abstract classes can't be
+    /// instantiated, and are the only classes that can have abstract methods.
     #[derive(Debug)]
     pub struct ClassDecAstNode<'a> {
         pub span: Span,
-        pub class_type: AstType<'a>,
-        pub extends: Option<AstType<'a>>,
-        pub implements: Option<AstType<'a>>,
-        pub fields: FieldList<'a, AstClassItemPublicity>,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub is_abstract: bool,
+        pub extensibility: AstClassExtensibility<'a>,
+        pub class_type: NameAndGenericsAstNode<'a>,
+        pub extends: SupertypeList<'a>,
+        pub implements: SupertypeList<'a>,
+        pub where_clause: Option<WhereClauseAstNode<'a>>,
+        pub fields: ClassFieldList<'a>,
+        pub statics: StaticFieldList<'a, AstClassItemPublicity>,
+        pub consts: AssociatedConstList<'a, AstClassItemPublicity>,
+        pub constructors: ConstructorList<'a, AstClassItemPublicity>,
         pub methods: MethodList<'a, AstClassItemPublicity>,
     }
+
+    /// This enum represents which classes are allowed to extend a class. The span
+    /// of `Sealed` and `Final` is that of their keyword.
+    /// ```text
+    /// class Shape {}                                 <== Open (anything can extend it)
+    ///
+    /// sealed class Shape permits Circle, Square {}   <== Sealed (only `Circle` and
+    ///                                                    `Square` can extend it)
+    ///
+    /// final class Circle extends Shape {}            <== Final (nothing can extend it)
+    /// ```
+    ///
+    /// (A sealed class that leaves out `permits` can be extended by any class in
+    /// the same file.)
+    #[derive(Debug)]
+    pub enum AstClassExtensibility<'a> {
+        Open,
+        Sealed(Span, SupertypeList<'a>),
+        Final(Span),
+    }
 }
 
 pub mod interfaces {
     use logos::Span;
 
+    use super::attributes::AttributeList;
     use super::methods::MethodList;
     use super::publicity::InterfaceMethodPublicity;
-    use super::types::AstType;
+    use super::types::{NameAndGenericsAstNode, SupertypeList, WhereClauseAstNode};
 
+    /// This struct represents an interface declaration. Interface methods are
+    /// always public, and may either be bodiless signatures or have default
+    /// implementations.
+    ///
+    /// ```text
+    ///              interface_type              extends
+    ///            ________|_______         ________|________
+    ///           |                |       |                 |
+    /// interface Container<T -> Eq> extends Sized, Iterable {
+    ///     fun contains(this, item -> T) -> bool;        <== methods[0]
+    ///     fun isEmpty(this) -> bool { this.size() == 0 } <== methods[1]
+    /// }
+    /// ```
     #[derive(Debug)]
     pub struct InterfaceDecAstNode<'a> {
         pub span: Span,
-        pub interface_type: AstType<'a>,
-        pub extends: Option<AstType<'a>>,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub interface_type: NameAndGenericsAstNode<'a>,
+        pub extends: SupertypeList<'a>,
+        pub where_clause: Option<WhereClauseAstNode<'a>>,
         pub methods: MethodList<'a, InterfaceMethodPublicity>,
     }
 }
 
+pub mod functions {
+    use logos::Span;
+
+    use super::attributes::AttributeList;
+    use super::documentation::Documented;
+    use super::expressions::ExpressionBlockAstNode;
+    use super::methods::AstMethodArgument;
+    use super::types::{AstType, NameAndGenericsAstNode, WhereClauseAstNode};
+
+    /// This struct represents a free (top-level) function declaration. It is just
+    /// like a method, minus the publicity and the ability to take `this`.
+    ///
+    /// ```text
+    ///                       FunctionDecAstNode::span
+    ///  ________________________________|________________________________
+    /// │                                                                 │
+    /// fun first<T>(list -> Vec<T>) -> Option<T> { return list.get(0); }
+    ///     |______| |______________|   |_______| |_______________________|
+    ///         │            │              │                 │
+    ///         │   FunctionDecAstNode::args│       FunctionDecAstNode::body
+    ///         │                           │
+    /// FunctionDecAstNode::function_type   FunctionDecAstNode::return_type
+    /// ```
+    #[derive(Debug)]
+    pub struct FunctionDecAstNode<'a> {
+        pub span: Span,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub function_type: NameAndGenericsAstNode<'a>,
+        pub args: Vec<Documented<'a, AstMethodArgument<'a>>>,
+        pub return_type: Option<AstType<'a>>,
+        pub where_clause: Option<WhereClauseAstNode<'a>>,
+        pub body: ExpressionBlockAstNode<'a>,
+    }
+}
+
+pub mod constants {
+    use logos::Span;
+
+    use super::attributes::AttributeList;
+    use super::documentation::Documented;
+    use super::expressions::AstExpression;
+    use super::patterns::AstBindingPattern;
+    use super::types::AstType;
+
+    /// This struct represents a constant declared outside of any function.
+    ///
+    /// ```text
+    ///                    ConstDecAstNode::span
+    ///  _______________________________|_______________________________
+    /// │                                                               │
+    /// const (MIN, MAX): (i32, i32) = (-2 ** 31, 2 ** 31 - 1);
+    ///       |________|  |________|   |_____________________|
+    ///           │            │                  │
+    /// ConstDecAstNode::pattern     ConstDecAstNode::value
+    ///                        │
+    ///           ConstDecAstNode::const_type
+    /// ```
+    ///
+    /// (Whether or not `value` can actually be evaluated at compile time is left
+    /// to later passes, see [`AstExpression::is_constant`].)
+    #[derive(Debug)]
+    pub struct ConstDecAstNode<'a> {
+        pub span: Span,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub pattern: AstBindingPattern<'a>,
+        pub const_type: Option<AstType<'a>>,
+        pub value: AstExpression<'a>,
+    }
+
+    /// This struct represents a constant that belongs to a struct, class, or enum,
+    /// which has a publicity like any other member of the type:
+    /// ```text
+    /// AssociatedConstAstNode::name
+    ///           │
+    /// pub const MAX: i32 = 10;
+    /// |_|            |_|   |_|
+    ///  │              │     │
+    ///  │              │  AssociatedConstAstNode::value
+    ///  │              │
+    ///  │  AssociatedConstAstNode::const_type
+    ///  │
+    /// AssociatedConstAstNode::publicity
+    /// ```
+    #[derive(Debug)]
+    pub struct AssociatedConstAstNode<'a, PublicityEnum> {
+        pub span: Span,
+        pub attributes: AttributeList<'a>,
+        pub publicity: PublicityEnum,
+        pub name: &'a str,
+        pub const_type: AstType<'a>,
+        pub value: AstExpression<'a>,
+    }
+
+    pub type AssociatedConstList<'a, PublicityEnum> =
+        Vec<Documented<'a, AssociatedConstAstNode<'a, PublicityEnum>>>;
+}
+
+pub mod attributes {
+    //! This module holds the representation of attributes (like
+    //! `#[derive(Eq, Hash)]` or `@Inherit(MySuperClass)`), which can be attached
+    //! to declarations, fields, enum cases, and methods.
+    //!
+    //! The parser doesn't give the arguments of an attribute any meaning, it just
+    //! keeps them as token trees for later passes to interpret.
+
+    use logos::Span;
+
+    use super::super::super::lexer::logos_lexer::LexerToken;
+
+    /// This struct represents a single attribute. Both of the attribute syntaxes
+    /// mean the same thing, and parse into the same struct.
+    ///
+    /// ```text
+    ///  AttributeAstNode::span        AttributeAstNode::span
+    ///  ___________|___________       ____________|____________
+    /// │                       │     │                         │
+    /// #[derive(Eq, SelectRandom)]   @Inherit(MySuperClass.thing)
+    ///   |____| |______________|      |_____| |_________________|
+    ///      │           │                │             │
+    ///      │  AttributeAstNode::args    │   AttributeAstNode::args
+    ///      │                            │
+    /// AttributeAstNode::path       AttributeAstNode::path
+    /// ```
+    #[derive(Debug)]
+    pub struct AttributeAstNode<'a> {
+        pub span: Span,
+        pub path: Vec<&'a str>,
+        pub args: Vec<AstTokenTree<'a>>,
+    }
+
+    pub type AttributeList<'a> = Vec<AttributeAstNode<'a>>;
+
+    /// This enum represents a tree of tokens, where every bracketed group of tokens
+    /// is a node and every other token is a leaf.
+    #[derive(Debug)]
+    pub enum AstTokenTree<'a> {
+        Token(Span, LexerToken<'a>),
+        Group(Span, AstDelimiter, Vec<AstTokenTree<'a>>),
+    }
+
+    impl<'a> AstTokenTree<'a> {
+        pub fn get_span(&self) -> Span {
+            match self {
+                Self::Token(span, _) | Self::Group(span, _, _) => span.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub enum AstDelimiter {
+        Parenthesis,
+        SquareBracket,
+        CurlyBrace,
+    }
+}
+
+pub mod documentation {
+    //! This module holds the wrapper that attaches documentation comments to
+    //! whatever comes after them. Every node that can be documented (other than a
+    //! top-level declaration, which keeps its documentation comments in a `docs`
+    //! field next to its attributes) is wrapped in the same way, so there is no
+    //! need for a "possibly documented" version of each one.
+
+    use logos::Span;
+
+    /// This struct represents a node along with the documentation comments (`///`
+    /// or `/** */`) written directly in front of it. Ordinary comments are never
+    /// kept, and `docs` is empty if there weren't any documentation comments.
+    ///
+    /// The span covers the documentation comments as well as the node:
+    /// ```text
+    /// /// The horizontal position.  ─┐
+    /// /// (Measured in pixels.)     ─┴── Documented::docs
+    /// pub x -> f64                  ──── Documented::node
+    /// ```
+    #[derive(Debug)]
+    pub struct Documented<'a, T> {
+        pub span: Span,
+        pub docs: Vec<&'a str>,
+        pub node: T,
+    }
+}
+
 pub mod fields {
+    use super::attributes::AttributeList;
+    use super::documentation::Documented;
+    use super::expressions::AstExpression;
+    use super::publicity::AstClassItemPublicity;
     use super::types::AstType;
     use logos::Span;
 
+    /// This struct represents a field with a type, which can also be given a
+    /// default value. (Defaults are assigned before any constructor runs.) The
+    /// type can be written after either `->` or `:`.
+    /// ```text
+    /// pub count -> u64 = 0
+    ///     |___|    |_|   |
+    ///      name     │  default
+    ///           field_type
+    /// ```
     #[derive(Debug)]
     pub struct FieldAstNode<'a, PublicityEnum> {
         pub span: Span,
+        pub attributes: AttributeList<'a>,
+        pub publicity: PublicityEnum,
+        pub name: &'a str,
+        pub field_type: AstType<'a>,
+        pub default: Option<AstExpression<'a>>,
+    }
+
+    pub type FieldList<'a, PublicityEnum> = Vec<Documented<'a, FieldAstNode<'a, PublicityEnum>>>;
+
+    /// This enum represents a field in the body of a class. Unlike struct fields,
+    /// class fields can leave out their type to re-declare a field that is
+    /// inherited from one of the superclasses:
+    /// ```text
+    /// class MyClass extends MySuperClass1, MySuperClass2 {
+    ///     pub declared -> String,
+    ///     |_____________________|
+    ///            Declared
+    ///
+    ///     @Inherit(MySuperClass2)
+    ///     pub thing,
+    ///     |________|
+    ///      Inherited
+    /// }
+    /// ```
+    #[derive(Debug)]
+    pub enum ClassFieldAstNode<'a> {
+        Declared(Span, Box<FieldAstNode<'a, AstClassItemPublicity>>),
+        Inherited(Span, InheritedFieldAstNode<'a>),
+    }
+
+    impl<'a> ClassFieldAstNode<'a> {
+        pub fn get_span(&self) -> Span {
+            match self {
+                Self::Declared(span, _) => span.clone(),
+                Self::Inherited(span, _) => span.clone(),
+            }
+        }
+
+        pub fn get_name(&self) -> &'a str {
+            match self {
+                Self::Declared(_, field) => field.name,
+                Self::Inherited(_, field) => field.name,
+            }
+        }
+    }
+
+    /// This struct represents a class field without a type, whose type comes from
+    /// the field with the same name in a superclass.
+    #[derive(Debug)]
+    pub struct InheritedFieldAstNode<'a> {
+        pub span: Span,
+        pub attributes: AttributeList<'a>,
+        pub publicity: AstClassItemPublicity,
+        pub name: &'a str,
+    }
+
+    pub type ClassFieldList<'a> = Vec<Documented<'a, ClassFieldAstNode<'a>>>;
+
+    /// This struct represents a static field (which only classes can have). It is
+    /// shared by every instance, and so has to be given an initial value:
+    /// ```text
+    /// pub static instances -> u64 = 0;
+    ///            |_______|    |_|   |
+    ///              name       │   value
+    ///                     field_type
+    /// ```
+    #[derive(Debug)]
+    pub struct StaticFieldAstNode<'a, PublicityEnum> {
+        pub span: Span,
+        pub attributes: AttributeList<'a>,
         pub publicity: PublicityEnum,
         pub name: &'a str,
         pub field_type: AstType<'a>,
+        pub value: AstExpression<'a>,
     }
 
-    pub type FieldList<'a, PublicityEnum> = Vec<FieldAstNode<'a, PublicityEnum>>;
+    pub type StaticFieldList<'a, PublicityEnum> =
+        Vec<Documented<'a, StaticFieldAstNode<'a, PublicityEnum>>>;
 }
 
 pub mod types {
     use logos::Span;
-    use std::borrow::Cow;
-    use std::collections::HashMap;
 
-    pub type Generics<'a> = (Span, HashMap<Cow<'a, str>, (Span, Option<AstType<'a>>)>);
+    use super::attributes::AttributeList;
+    use super::documentation::Documented;
+
+    /// This struct represents the generic parameters that a declaration (or a
+    /// constraint block) introduces, in the order they were declared:
+    /// ```text
+    /// struct Map<K -> Hash, V> {
+    ///            |_______|  |
+    ///                0      1
+    /// ```
+    #[derive(Debug)]
+    pub struct GenericParams<'a> {
+        pub span: Span,
+        pub params: Vec<Documented<'a, GenericParam<'a>>>,
+    }
+
+    /// This struct represents a single generic parameter. Every bound is a type
+    /// with its own span:
+    /// ```text
+    /// T -> Eq & Hash = String
+    ///      |_|  |__|   |____|
+    ///        bounds    default
+    /// ```
+    #[derive(Debug)]
+    pub struct GenericParam<'a> {
+        pub span: Span,
+        pub name: &'a str,
+        pub bounds: Vec<AstType<'a>>,
+        pub default: Option<AstType<'a>>,
+    }
+
+    /// This struct represents a `where` clause, which bounds types after their
+    /// generics have been declared:
+    /// ```text
+    /// fun sort<T, U>(list -> T[]) where T -> Ord & Clone, U -> Eq {
+    ///                             |__________________________|
+    ///                                 |_______________|  |_____|
+    ///                                     predicates
+    /// ```
+    #[derive(Debug)]
+    pub struct WhereClauseAstNode<'a> {
+        pub span: Span,
+        pub predicates: Vec<WherePredicateAstNode<'a>>,
+    }
 
     #[derive(Debug)]
+    pub struct WherePredicateAstNode<'a> {
+        pub span: Span,
+        pub bounded_type: AstType<'a>,
+        pub bounds: Vec<AstType<'a>>,
+    }
+
+    /// This struct represents the generic arguments given to a type, in order:
+    /// ```text
+    /// Map<String, Int>
+    ///     |____|  |_|
+    ///       0      1
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct GenericArgs<'a> {
+        pub span: Span,
+        pub args: Vec<AstType<'a>>,
+    }
+
+    /// This struct represents the name that a declaration introduces, along with
+    /// its generic parameters (if it has any):
+    /// ```text
+    /// struct Map<K -> Hash, V> {
+    ///        |__||____________|
+    ///        name generic_params
+    /// ```
+    #[derive(Debug)]
+    pub struct NameAndGenericsAstNode<'a> {
+        pub span: Span,
+        pub name: &'a str,
+        pub generic_params: Option<GenericParams<'a>>,
+    }
+
+    impl<'a> NameAndGenericsAstNode<'a> {
+        /// Gets the generic parameters in order, which is empty if none were
+        /// declared.
+        pub fn get_generic_params(&self) -> &[Documented<'a, GenericParam<'a>>] {
+            match &self.generic_params {
+                Some(generic_params) => &generic_params.params,
+                None => &[],
+            }
+        }
+    }
+
+    /// This type alias represents the ordered list of types in an `extends` or
+    /// `implements` clause. It is empty if the clause was left out.
+    ///
+    /// The types can be seperated by either `,` or `&`, which mean the same thing:
+    /// ```text
+    /// class MyClass extends MySuperClass1, MySuperClass2 implements Eq & Hash {
+    ///                       |___________|  |___________|            |__|   |__|
+    ///                             0              1                   0      1
+    /// ```
+    pub type SupertypeList<'a> = Vec<AstType<'a>>;
+
+    /// This enum represents a type anywhere one can be written:
+    /// ```text
+    /// fun(Map<String, Int>, (Hand, Hand)) -> String[]?
+    ///     |______________|  |__________|     |_____|
+    ///         GenericOf         Tuple         Array
+    ///                                        |______|
+    ///                                        Optional
+    /// |_______________________________________________|
+    ///                      Function
+    /// ```
+    #[derive(Clone, Debug)]
     pub enum AstType<'a> {
         RootName(Span, &'a str),
         MemberOf(Span, Box<AstType<'a>>, &'a str),
-        GenericOf(Span, Box<AstType<'a>>, Generics<'a>),
+        GenericOf(Span, Box<AstType<'a>>, GenericArgs<'a>),
+        Tuple(Span, Vec<AstType<'a>>),
+        Array(Span, Box<AstType<'a>>),
+        Function(Span, Vec<AstType<'a>>, Option<Box<AstType<'a>>>),
+        Optional(Span, Box<AstType<'a>>),
     }
 
     impl<'a> AstType<'a> {
@@ -339,7 +979,22 @@ pub mod types {
             match self {
                 Self::RootName(span, _)
                 | Self::MemberOf(span, _, _)
-                | Self::GenericOf(span, _, _) => span.clone(),
+                | Self::GenericOf(span, _, _)
+                | Self::Tuple(span, _)
+                | Self::Array(span, _)
+                | Self::Function(span, _, _)
+                | Self::Optional(span, _) => span.clone(),
+            }
+        }
+
+        /// Gets the name that a (possibly generic or namespaced) named type is
+        /// referred to by, which is `None` for types that don't have a name (like
+        /// tuples).
+        pub fn get_name(&self) -> Option<&'a str> {
+            match self {
+                Self::RootName(_, name) | Self::MemberOf(_, _, name) => Some(name),
+                Self::GenericOf(_, base, _) => base.get_name(),
+                _ => None,
             }
         }
     }
@@ -347,7 +1002,9 @@ pub mod types {
     #[derive(Debug)]
     pub struct TypeAliasAstNode<'a> {
         pub span: Span,
-        pub aliased_type: AstType<'a>,
+        pub attributes: AttributeList<'a>,
+        pub docs: Vec<&'a str>,
+        pub aliased_type: NameAndGenericsAstNode<'a>,
         pub orig_type: AstType<'a>,
     }
 }
@@ -355,6 +1012,8 @@ pub mod types {
 pub mod patterns {
     use logos::Span;
 
+    use super::expressions::AstLiteral;
+
     #[derive(Debug)]
     pub enum AstDestructuringPattern<'a> {
         Name(Span, &'a str),
@@ -372,20 +1031,100 @@ pub mod patterns {
         }
     }
 
+    /// This enum represents an irrefutable pattern that binds variables, as used by
+    /// `let` and `const` declarations.
+    ///
+    /// ```text
+    ///                 Tuple(_, [.., .., ..])
+    ///                 _________|_________
+    ///                │                   │
+    /// let (mut first, _, (second, third)) = ...;
+    ///      |_______|  |  |______________|
+    ///          │      │          │
+    ///   MutableName   │        Tuple
+    ///              Ignored
+    /// ```
+    #[derive(Debug)]
+    pub enum AstBindingPattern<'a> {
+        Name(Span, &'a str),
+        MutableName(Span, &'a str),
+        Ignored(Span),
+        Tuple(Span, Vec<AstBindingPattern<'a>>),
+    }
+
+    impl<'a> AstBindingPattern<'a> {
+        pub fn get_span(&self) -> Span {
+            match self {
+                Self::Name(span, _)
+                | Self::MutableName(span, _)
+                | Self::Ignored(span)
+                | Self::Tuple(span, _) => span.clone(),
+            }
+        }
+    }
+
+    /// This enum represents a refutable pattern, as used by the arms of a `match`.
+    ///
+    /// A lone name (like `Rock` or `hand`) could either be a binding or a reference
+    /// to a unit enum case, so it is kept as a `Name` for later passes to resolve.
+    ///
+    /// ```text
+    ///           Or(_, [.., ..])
+    ///  ________________|________________
+    /// │                                 │
+    /// Some((1..5, _)) | RoundResult.Draw
+    /// |_____________|   |______________|
+    ///        │                 │
+    /// Case(_, ["Some"], [..])  Path(_, ["RoundResult", "Draw"])
+    ///       |
+    ///  Tuple(_, [Range(_, 1, 5), Wildcard(_)])
+    /// ```
     #[derive(Debug)]
-    pub enum AstPattern {}
+    pub enum AstPattern<'a> {
+        Wildcard(Span),
+        Literal(Span, AstLiteral),
+        Range(Span, AstLiteral, AstLiteral),
+
+        Name(Span, &'a str),
+        MutableName(Span, &'a str),
+        Path(Span, Vec<&'a str>),
+        Case(Span, Vec<&'a str>, Vec<AstPattern<'a>>),
+
+        Tuple(Span, Vec<AstPattern<'a>>),
+        Or(Span, Vec<AstPattern<'a>>),
+    }
+
+    impl<'a> AstPattern<'a> {
+        pub fn get_span(&self) -> Span {
+            match self {
+                Self::Wildcard(span)
+                | Self::Literal(span, _)
+                | Self::Range(span, _, _)
+                | Self::Name(span, _)
+                | Self::MutableName(span, _)
+                | Self::Path(span, _)
+                | Self::Case(span, _, _)
+                | Self::Tuple(span, _)
+                | Self::Or(span, _) => span.clone(),
+            }
+        }
+    }
 }
 
 pub mod methods {
     use logos::Span;
 
-    use super::expressions::ExpressionBlockAstNode;
-    use super::types::{AstType, Generics};
+    use super::attributes::AttributeList;
+    use super::documentation::Documented;
+    use super::expressions::{
+        AstBinaryOperator, AstExpression, AstUnaryOperator, ExpressionBlockAstNode,
+    };
+    use super::types::{AstType, GenericParams, NameAndGenericsAstNode, WhereClauseAstNode};
 
     #[derive(Debug)]
     pub enum MethodOrConstraintAstNode<'a, Publicity> {
-        Method(Span, PossiblyDocumentedMethodAstNode<'a, Publicity>),
-        Constraint(Span, Generics<'a>, MethodList<'a, Publicity>),
+        Method(Span, Box<Documented<'a, MethodAstNode<'a, Publicity>>>),
+        Constraint(Span, GenericParams<'a>, MethodList<'a, Publicity>),
     }
 
     impl<'a, Publicity> MethodOrConstraintAstNode<'a, Publicity> {
@@ -412,34 +1151,127 @@ pub mod methods {
     }
 
     #[derive(Debug)]
-    pub enum PossiblyDocumentedMethodAstNode<'a, Publicity> {
-        BaseMethod(Span, MethodAstNode<'a, Publicity>),
-        DocumentedMethod(
-            Span,
-            &'a str,
-            Box<PossiblyDocumentedMethodAstNode<'a, Publicity>>,
-        ),
+    pub struct MethodAstNode<'a, Publicity> {
+        pub span: Span,
+        pub attributes: AttributeList<'a>,
+        pub publicity: Publicity,
+        /// Abstract methods never have a body, and have to be implemented by
+        /// every (non-abstract) subclass.
+        pub is_abstract: bool,
+        /// This is set by `override`, which marks a method that replaces one of a
+        /// superclass.
+        pub is_override: bool,
+        /// For operator overloads, the name of `new_type` is the operator's method
+        /// name (like `operator+`), and this is the operator it implements.
+        pub operator: Option<AstOverloadableOperator>,
+        pub new_type: NameAndGenericsAstNode<'a>,
+        pub args: Vec<Documented<'a, AstMethodArgument<'a>>>,
+        pub return_type: Option<AstType<'a>>,
+        pub where_clause: Option<WhereClauseAstNode<'a>>,
+        /// This is `None` for bodiless signatures (like those in interfaces, or
+        /// abstract methods).
+        pub body: Option<ExpressionBlockAstNode<'a>>,
     }
 
-    impl<'a, Publicity> PossiblyDocumentedMethodAstNode<'a, Publicity> {
-        pub fn get_span(&self) -> Span {
+    pub type MethodList<'a, Publicity> = Vec<MethodOrConstraintAstNode<'a, Publicity>>;
+
+    /// This enum represents the operators that a type can implement for itself,
+    /// by declaring a method like `fun operator+(this, other -> This) -> This`.
+    ///
+    /// `Subscript` is declared as `operator[]` and `Call` as `operator()`. The
+    /// short-circuiting `&&` and `||` (and the postfix `?`) can't be overloaded.
+    ///
+    /// (`operator-` is `Negate` if it only takes `this`, and `Subtract` otherwise.)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AstOverloadableOperator {
+        Unary(AstUnaryOperator),
+        Binary(AstBinaryOperator),
+        Subscript,
+        Call,
+    }
+
+    impl AstOverloadableOperator {
+        /// Gets the operator as it is written after `operator` in a declaration.
+        pub fn get_symbol(&self) -> &'static str {
+            &self.get_method_name()["operator".len()..]
+        }
+
+        /// Gets the name that a method implementing this operator is given.
+        pub fn get_method_name(&self) -> &'static str {
             match self {
-                Self::BaseMethod(span, _) | Self::DocumentedMethod(span, _, _) => span.clone(),
+                Self::Unary(AstUnaryOperator::Negate) => "operator-",
+                Self::Unary(AstUnaryOperator::Not) => "operator!",
+                Self::Unary(AstUnaryOperator::Propagate) => "operator?",
+
+                Self::Binary(operator) => match operator {
+                    AstBinaryOperator::Range => "operator..",
+                    AstBinaryOperator::Or => "operator||",
+                    AstBinaryOperator::And => "operator&&",
+                    AstBinaryOperator::EqualTo => "operator==",
+                    AstBinaryOperator::NotEqualTo => "operator!=",
+                    AstBinaryOperator::LessThan => "operator<",
+                    AstBinaryOperator::GreaterThan => "operator>",
+                    AstBinaryOperator::LessThanOrEqualTo => "operator<=",
+                    AstBinaryOperator::GreaterThanOrEqualTo => "operator>=",
+                    AstBinaryOperator::BitOr => "operator|",
+                    AstBinaryOperator::BitXor => "operator^",
+                    AstBinaryOperator::BitAnd => "operator&",
+                    AstBinaryOperator::ShiftLeft => "operator<<",
+                    AstBinaryOperator::ShiftRight => "operator>>",
+                    AstBinaryOperator::Add => "operator+",
+                    AstBinaryOperator::Subtract => "operator-",
+                    AstBinaryOperator::Multiply => "operator*",
+                    AstBinaryOperator::Divide => "operator/",
+                    AstBinaryOperator::Modulo => "operator%",
+                    AstBinaryOperator::Power => "operator**",
+                },
+
+                Self::Subscript => "operator[]",
+                Self::Call => "operator()",
             }
         }
     }
 
+    /// This struct represents a constructor, which can chain to the constructors
+    /// of the classes it extends before its own body runs. Constructors always
+    /// act on a `mut this`, so it isn't written out in the arguments.
+    /// ```text
+    ///                                 super_calls
+    ///                   ___________________|___________________
+    ///                  |                                       |
+    /// pub constructor(name -> String) : super<Named>(name), super<Counted>() {
+    ///                |______________|                                       |
+    ///                       │         ...                                   │
+    ///                      args     }                                       │
+    ///                               |_______________________________________|
+    ///                                                  body
+    /// ```
     #[derive(Debug)]
-    pub struct MethodAstNode<'a, Publicity> {
+    pub struct ConstructorAstNode<'a, Publicity> {
         pub span: Span,
+        pub attributes: AttributeList<'a>,
         pub publicity: Publicity,
-        pub new_type: AstType<'a>,
-        pub args: Vec<AstMethodArgument<'a>>,
-        pub return_type: Option<AstType<'a>>,
-        pub body: ExpressionBlockAstNode,
+        pub args: Vec<Documented<'a, AstMethodArgument<'a>>>,
+        pub super_calls: Vec<SuperCallAstNode<'a>>,
+        pub body: ExpressionBlockAstNode<'a>,
     }
 
-    pub type MethodList<'a, Publicity> = Vec<MethodOrConstraintAstNode<'a, Publicity>>;
+    pub type ConstructorList<'a, Publicity> =
+        Vec<Documented<'a, ConstructorAstNode<'a, Publicity>>>;
+
+    /// This struct represents a call to the constructor of one of the classes
+    /// being extended. `parent` can only be left out if there is just one:
+    /// ```text
+    /// super<Named>(name)
+    ///       |___| |__|
+    ///      parent  args
+    /// ```
+    #[derive(Debug)]
+    pub struct SuperCallAstNode<'a> {
+        pub span: Span,
+        pub parent: Option<AstType<'a>>,
+        pub args: Vec<AstExpression<'a>>,
+    }
 }
 
 pub mod publicity {
@@ -463,31 +1295,427 @@ pub mod publicity {
     pub enum InterfaceMethodPublicity {
         Public,
     }
+
+    #[derive(Debug)]
+    pub enum EnumCaseFieldPublicity {
+        Public,
+    }
 }
 
 pub mod expressions {
-    //! TODOS:
-    //! * CONTROL FLOW
-    //! * Loops (while, repeat, for)
-    //! * Break, continue, return
-    //! * If statements
-    //! * Match statements
-    //! * Match cases
-    //! 
-    //! OTHER TODOS:
-    //! * Operator usage
-    //! * Assignment
-    //! * Function call
-    //! * Member lookup
-    //! * Subscript
-    //! * Literal
-    //! * Type casting
-    //! * Variable declarations
-
-    
-    
-    #[derive(Debug)]
-    pub struct ExpressionBlockAstNode {
-        
+    //! This module holds everything that can show up inside of a method body: the
+    //! block node that methods own, the statements inside of blocks, and the
+    //! expression tree itself.
+
+    use logos::Span;
+
+    use super::patterns::{AstBindingPattern, AstPattern};
+    use super::types::AstType;
+
+    /// This struct represents a block of code surrounded by curly braces. The last
+    /// expression in the block (if it isn't followed by a semicolon) is the value
+    /// of the block.
+    ///
+    /// ```text
+    ///          ExpressionBlockAstNode::span
+    ///  ___________________|___________________
+    /// │                                       │
+    /// {
+    ///     doSomething();    <== statements[0]
+    ///     doSomethingElse() <== value
+    /// }
+    /// ```
+    #[derive(Debug)]
+    pub struct ExpressionBlockAstNode<'a> {
+        pub span: Span,
+        pub statements: Vec<AstStatement<'a>>,
+        pub value: Option<Box<AstExpression<'a>>>,
+    }
+
+    /// This enum represents a single statement inside of a block.
+    ///
+    /// `Expression(Span, AstExpression<'a>)` (an expression evaluated for its side
+    /// effects),
+    ///
+    /// `VariableDec(Span, VariableDecAstNode<'a>)` (a `let` or `const`
+    /// declaration), and
+    ///
+    /// `Assignment(Span, AstExpression<'a>, AstExpression<'a>)` (an assignment of
+    /// the second expression to the place described by the first)
+    ///
+    /// (The span of a statement includes its trailing semicolon, if it has one.)
+    #[derive(Debug)]
+    pub enum AstStatement<'a> {
+        Expression(Span, AstExpression<'a>),
+        VariableDec(Span, VariableDecAstNode<'a>),
+        Assignment(Span, AstExpression<'a>, AstExpression<'a>),
+    }
+
+    impl<'a> AstStatement<'a> {
+        pub fn get_span(&self) -> Span {
+            match self {
+                Self::Expression(span, _)
+                | Self::VariableDec(span, _)
+                | Self::Assignment(span, _, _) => span.clone(),
+            }
+        }
+    }
+
+    /// This struct represents a local variable declaration.
+    ///
+    /// Here is what each struct field refers to:
+    /// ```text
+    ///                   VariableDecAstNode::span
+    ///  ____________________________|____________________________
+    /// │                                                         │
+    /// let (mut first, second): (i32, String) = getTheTuple();
+    /// |_| |_____________________| |___________| |____________|
+    ///  │             │                 │               │
+    ///  │  VariableDecAstNode::pattern  │    VariableDecAstNode::value
+    ///  │                               │
+    /// VariableDecAstNode::kind  VariableDecAstNode::var_type
+    /// ```
+    ///
+    /// (`value` is only optional for `let` declarations.)
+    #[derive(Debug)]
+    pub struct VariableDecAstNode<'a> {
+        pub span: Span,
+        pub kind: AstVariableKind,
+        pub pattern: AstBindingPattern<'a>,
+        pub var_type: Option<AstType<'a>>,
+        pub value: Option<AstExpression<'a>>,
+    }
+
+    #[derive(Debug)]
+    pub enum AstVariableKind {
+        Let,
+        Const,
+    }
+
+    /// This is the sum type that represents every kind of expression.
+    ///
+    /// Binary expressions are built by precedence climbing, so this:
+    /// ```text
+    /// a + b * c as i32
+    /// ```
+    /// parses into this:
+    /// ```tree-representation
+    ///   Binary(_, Add, ., .)
+    ///                 /   \
+    ///           Name "a"   Binary(_, Multiply, ., .)
+    ///                                         /   \
+    ///                                  Name "b"   Cast(_, ., i32)
+    ///                                                     |
+    ///                                                  Name "c"
+    /// ```
+    #[derive(Debug)]
+    pub enum AstExpression<'a> {
+        Literal(Span, AstLiteral),
+        Name(Span, &'a str),
+        This(Span),
+        BigThis(Span),
+
+        Parenthesized(Span, Box<AstExpression<'a>>),
+        Tuple(Span, Vec<AstExpression<'a>>),
+        Array(Span, Vec<AstExpression<'a>>),
+        Block(Span, ExpressionBlockAstNode<'a>),
+
+        If(Span, IfAstNode<'a>),
+        While(Span, WhileAstNode<'a>),
+        Loop(Span, LoopAstNode<'a>),
+        For(Span, ForAstNode<'a>),
+        Match(Span, MatchAstNode<'a>),
+
+        Closure(Span, Box<ClosureAstNode<'a>>),
+
+        Break(Span, Option<&'a str>),
+        Continue(Span, Option<&'a str>),
+        Return(Span, Option<Box<AstExpression<'a>>>),
+        Yield(Span, Option<Box<AstExpression<'a>>>),
+
+        Unary(Span, AstUnaryOperator, Box<AstExpression<'a>>),
+        Binary(
+            Span,
+            AstBinaryOperator,
+            Box<AstExpression<'a>>,
+            Box<AstExpression<'a>>,
+        ),
+        Cast(Span, Box<AstExpression<'a>>, AstType<'a>),
+
+        Call(Span, Box<AstExpression<'a>>, Vec<AstExpression<'a>>),
+        /// A macro invocation, like `println!("{}", x)`. The name is the part
+        /// before the `!`.
+        MacroCall(Span, &'a str, Vec<AstExpression<'a>>),
+        MemberAccess(Span, Box<AstExpression<'a>>, &'a str),
+        Subscript(Span, Box<AstExpression<'a>>, Box<AstExpression<'a>>),
+    }
+
+    impl<'a> AstExpression<'a> {
+        pub fn get_span(&self) -> Span {
+            match self {
+                Self::Literal(span, _)
+                | Self::Name(span, _)
+                | Self::This(span)
+                | Self::BigThis(span)
+                | Self::Parenthesized(span, _)
+                | Self::Tuple(span, _)
+                | Self::Array(span, _)
+                | Self::Block(span, _)
+                | Self::If(span, _)
+                | Self::While(span, _)
+                | Self::Loop(span, _)
+                | Self::For(span, _)
+                | Self::Match(span, _)
+                | Self::Closure(span, _)
+                | Self::Break(span, _)
+                | Self::Continue(span, _)
+                | Self::Return(span, _)
+                | Self::Yield(span, _)
+                | Self::Unary(span, _, _)
+                | Self::Binary(span, _, _, _)
+                | Self::Cast(span, _, _)
+                | Self::Call(span, _, _)
+                | Self::MacroCall(span, _, _)
+                | Self::MemberAccess(span, _, _)
+                | Self::Subscript(span, _, _) => span.clone(),
+            }
+        }
+
+        /// Whether or not this expression describes a place that can be assigned to.
+        pub fn is_assignable(&self) -> bool {
+            match self {
+                Self::Name(_, _) | Self::MemberAccess(_, _, _) | Self::Subscript(_, _, _) => true,
+                Self::Parenthesized(_, inner) => inner.is_assignable(),
+                _ => false,
+            }
+        }
+
+        /// Whether or not this expression is built only out of literals, names (which
+        /// must themselves refer to constants), and operators on those, meaning it
+        /// could be evaluated at compile time.
+        ///
+        /// Calls, blocks, and control flow are never considered constant.
+        pub fn is_constant(&self) -> bool {
+            match self {
+                Self::Literal(_, _) | Self::Name(_, _) | Self::BigThis(_) => true,
+                Self::Parenthesized(_, inner)
+                | Self::Unary(_, _, inner)
+                | Self::Cast(_, inner, _)
+                | Self::MemberAccess(_, inner, _) => inner.is_constant(),
+                Self::Binary(_, _, lhs, rhs) | Self::Subscript(_, lhs, rhs) => {
+                    lhs.is_constant() && rhs.is_constant()
+                }
+                Self::Tuple(_, elements) | Self::Array(_, elements) => {
+                    elements.iter().all(Self::is_constant)
+                }
+                _ => false,
+            }
+        }
+
+        /// Whether or not this expression ends in a block, and therefore doesn't need
+        /// a semicolon to be used as a statement.
+        pub fn is_block_like(&self) -> bool {
+            matches!(
+                self,
+                Self::Block(_, _)
+                    | Self::If(_, _)
+                    | Self::While(_, _)
+                    | Self::Loop(_, _)
+                    | Self::For(_, _)
+                    | Self::Match(_, _)
+            )
+        }
+    }
+
+    /// This struct represents an `if` expression. `else if` chains are represented
+    /// by an `else_branch` that is itself an `If` expression.
+    ///
+    /// ```text
+    ///                      IfAstNode::span
+    ///  ___________________________|___________________________
+    /// │                                                       │
+    /// if a == b { doThing() } else if c { doOther() } else { 1 }
+    ///    |____| |___________|      |____________________________|
+    ///      │          │                          │
+    ///      │   IfAstNode::then_block    IfAstNode::else_branch
+    ///      │                           (either an `If` or a `Block`)
+    /// IfAstNode::condition
+    /// ```
+    #[derive(Debug)]
+    pub struct IfAstNode<'a> {
+        pub span: Span,
+        pub condition: Box<AstExpression<'a>>,
+        pub then_block: ExpressionBlockAstNode<'a>,
+        pub else_branch: Option<Box<AstExpression<'a>>>,
+    }
+
+    /// This struct represents a `while` loop, with an optional label.
+    ///
+    /// ```text
+    /// outer: while i < 10 { i = i + 1; }
+    /// |___|        |____| |____________|
+    ///   │            │          │
+    ///   │  WhileAstNode::condition   WhileAstNode::body
+    ///   │
+    /// WhileAstNode::label
+    /// ```
+    #[derive(Debug)]
+    pub struct WhileAstNode<'a> {
+        pub span: Span,
+        pub label: Option<&'a str>,
+        pub condition: Box<AstExpression<'a>>,
+        pub body: ExpressionBlockAstNode<'a>,
+    }
+
+    /// This struct represents an infinite `loop`, with an optional label.
+    #[derive(Debug)]
+    pub struct LoopAstNode<'a> {
+        pub span: Span,
+        pub label: Option<&'a str>,
+        pub body: ExpressionBlockAstNode<'a>,
+    }
+
+    /// This struct represents a `for` loop over an iterable, with an optional label.
+    ///
+    /// ```text
+    /// for (first, second) in pairs.toIter() { println(first); }
+    ///     |_____________|    |____________| |__________________|
+    ///            │                  │                 │
+    ///   ForAstNode::pattern         │          ForAstNode::body
+    ///                               │
+    ///                      ForAstNode::iterable
+    /// ```
+    #[derive(Debug)]
+    pub struct ForAstNode<'a> {
+        pub span: Span,
+        pub label: Option<&'a str>,
+        pub pattern: AstBindingPattern<'a>,
+        pub iterable: Box<AstExpression<'a>>,
+        pub body: ExpressionBlockAstNode<'a>,
+    }
+
+    /// This struct represents a `match` expression.
+    ///
+    /// ```text
+    ///               MatchAstNode::span
+    ///  ____________________|____________________
+    /// │                                         │
+    /// match stringToHand(input) {
+    ///       |_________________|
+    ///                │
+    ///     MatchAstNode::scrutinee
+    ///
+    ///     Some(hand) => play(hand),  <== arms[0]
+    ///     None => continue,          <== arms[1]
+    /// }
+    /// ```
+    #[derive(Debug)]
+    pub struct MatchAstNode<'a> {
+        pub span: Span,
+        pub scrutinee: Box<AstExpression<'a>>,
+        pub arms: Vec<MatchArmAstNode<'a>>,
+    }
+
+    /// This struct represents a single arm of a `match` expression.
+    ///
+    /// ```text
+    ///             MatchArmAstNode::span
+    ///  _________________________|_________________________
+    /// │                                                   │
+    /// Some(count) if count > 0 => println("Got a number!"),
+    /// |_________|    |_______|    |______________________|
+    ///      │             │                   │
+    ///      │  MatchArmAstNode::guard  MatchArmAstNode::body
+    ///      │
+    /// MatchArmAstNode::pattern
+    /// ```
+    #[derive(Debug)]
+    pub struct MatchArmAstNode<'a> {
+        pub span: Span,
+        pub pattern: AstPattern<'a>,
+        pub guard: Option<AstExpression<'a>>,
+        pub body: AstExpression<'a>,
+    }
+
+    /// This struct represents a closure (an anonymous function), whose parameters
+    /// can be left untyped. The body is either a block or (after `=>`) a single
+    /// expression.
+    ///
+    /// ```text
+    ///                  ClosureAstNode::span
+    ///  ______________________|______________________
+    /// │                                             │
+    /// fun(count -> i32, step) -> i32 => count + step
+    ///    |_______________|       |_|    |__________|
+    ///            │                │          │
+    ///   ClosureAstNode::params    │   ClosureAstNode::body
+    ///                             │
+    ///                ClosureAstNode::return_type
+    /// ```
+    #[derive(Debug)]
+    pub struct ClosureAstNode<'a> {
+        pub span: Span,
+        pub params: Vec<ClosureParamAstNode<'a>>,
+        pub return_type: Option<AstType<'a>>,
+        pub body: AstClosureBody<'a>,
+    }
+
+    #[derive(Debug)]
+    pub struct ClosureParamAstNode<'a> {
+        pub span: Span,
+        pub name: &'a str,
+        pub param_type: Option<AstType<'a>>,
+    }
+
+    #[derive(Debug)]
+    pub enum AstClosureBody<'a> {
+        Block(ExpressionBlockAstNode<'a>),
+        Expression(Box<AstExpression<'a>>),
+    }
+
+    #[derive(Debug)]
+    pub enum AstLiteral {
+        String(String),
+        Char(char),
+        Integer(i64),
+        Word(u64),
+        Float(f64),
+        Bool(bool),
+    }
+
+    /// `Negate` is `-x`, `Not` is `!x`, and `Propagate` is the postfix `x?`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AstUnaryOperator {
+        Negate,
+        Not,
+        Propagate,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AstBinaryOperator {
+        Range,
+
+        Or,
+        And,
+
+        EqualTo,
+        NotEqualTo,
+        LessThan,
+        GreaterThan,
+        LessThanOrEqualTo,
+        GreaterThanOrEqualTo,
+
+        BitOr,
+        BitXor,
+        BitAnd,
+        ShiftLeft,
+        ShiftRight,
+
+        Add,
+        Subtract,
+        Multiply,
+        Divide,
+        Modulo,
+        Power,
     }
 }
@@ -0,0 +1,160 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::attributes::{AstDelimiter, AstTokenTree, AttributeAstNode, AttributeList};
+use super::parse_error::ParseError;
+use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
+
+/// Parses every attribute until something that isn't one is found. If there are
+/// no attributes, nothing is consumed.
+///
+/// (Comments after the last attribute are left alone, so that they can still be
+/// treated as documentation.)
+pub fn parse_attributes<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AttributeList<'a>, ParseError<'a>> {
+    let mut attributes = Vec::new();
+
+    loop {
+        let position = lxr.save_position();
+
+        flush_comments(lxr);
+
+        if let Some(LexerToken::Hash | LexerToken::AtSign) = lxr.peek() {
+            lxr.next();
+            attributes.push(parse_attribute(lxr)?);
+        } else {
+            lxr.return_to_position(position);
+            break;
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Parses a single attribute, assuming that the `#` or `@` that begins it has
+/// already been consumed.
+pub fn parse_attribute<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AttributeAstNode<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+    let is_bracketed = matches!(lxr.slice(), Some("#"));
+
+    if is_bracketed {
+        expect_token(
+            lxr,
+            LexerToken::LeftSquareBracket,
+            &["`[` (to open the attribute)"],
+        )?;
+    }
+
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::Identifier(""),
+        &["identifier (to name the attribute)"],
+    )?;
+    let mut path = vec![lxr.slice().unwrap()];
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::MemberAccess) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
+        }
+
+        flush_comments(lxr);
+
+        expect_token(
+            lxr,
+            LexerToken::Identifier(""),
+            &["identifier (as part of the attribute's path)"],
+        )?;
+        path.push(lxr.slice().unwrap());
+    }
+
+    let args = if let Some(LexerToken::LeftParenthesis) = lxr.peek() {
+        lxr.next();
+        parse_token_trees_until_closed(lxr, LexerToken::RightParenthesis)?
+    } else {
+        Vec::new()
+    };
+
+    if is_bracketed {
+        flush_comments(lxr);
+
+        expect_token(
+            lxr,
+            LexerToken::RightSquareBracket,
+            &["`]` (to close the attribute)"],
+        )?;
+    }
+
+    Ok(AttributeAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        path,
+        args,
+    })
+}
+
+/// Parses token trees up to and including `closing_token`, which must balance
+/// the opening bracket that was just consumed.
+fn parse_token_trees_until_closed<'a>(
+    lxr: &mut LexerStruct<'a>,
+    closing_token: LexerToken<'a>,
+) -> Result<Vec<AstTokenTree<'a>>, ParseError<'a>> {
+    let mut token_trees = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        let token = lxr.next();
+        let span = lxr.span().unwrap_or(usize::MAX..usize::MAX);
+
+        let delimiter_and_closing_token = match token {
+            Some(ref token) if *token == closing_token => break,
+
+            Some(LexerToken::LeftParenthesis) => {
+                (AstDelimiter::Parenthesis, LexerToken::RightParenthesis)
+            }
+            Some(LexerToken::LeftSquareBracket) => {
+                (AstDelimiter::SquareBracket, LexerToken::RightSquareBracket)
+            }
+            Some(LexerToken::LeftCurlyBrace) => {
+                (AstDelimiter::CurlyBrace, LexerToken::RightCurlyBrace)
+            }
+
+            Some(
+                LexerToken::RightParenthesis
+                | LexerToken::RightSquareBracket
+                | LexerToken::RightCurlyBrace,
+            )
+            | None => {
+                return Err(call_error(
+                    lxr,
+                    token,
+                    &["a matching closing bracket (to balance the attribute's arguments)"],
+                    true,
+                ))
+            }
+
+            Some(token) => {
+                token_trees.push(AstTokenTree::Token(span, token));
+                continue;
+            }
+        };
+
+        let (delimiter, group_closing_token) = delimiter_and_closing_token;
+        let group = parse_token_trees_until_closed(lxr, group_closing_token)?;
+
+        token_trees.push(AstTokenTree::Group(
+            span.start..lxr.span().unwrap().end,
+            delimiter,
+            group,
+        ));
+    }
+
+    Ok(token_trees)
+}
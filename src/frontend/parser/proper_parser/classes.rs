@@ -1,32 +1,111 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::ast::classes::ClassDecAstNode;
-use super::extends_implements::{parse_extends, parse_implements};
-use super::fields::parse_fields_until_none_are_left;
-use super::methods::parse_methods_until_none_are_found;
+use super::ast::classes::{AstClassExtensibility, ClassDecAstNode};
+use super::extends_implements::{parse_extends, parse_implements, parse_permits};
+use super::fields::{parse_class_field, parse_fields_until_none_are_left};
+use super::members::parse_members_until_none_are_found;
 use super::parse_error::{combine_parse_errors, ParseError};
-use super::types::parse_name_and_generics;
-use super::utility_things::{expect_token, LexerStruct};
+use super::types::{parse_name_and_generics, parse_where_clause};
+use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
 
+/// Parses a class declaration without any modifiers, assuming that the `class`
+/// keyword has already been consumed.
 pub fn parse_class<'a>(lxr: &mut LexerStruct<'a>) -> Result<ClassDecAstNode<'a>, ParseError<'a>> {
     let start_span = lxr.span().unwrap().start;
 
+    parse_class_rest(lxr, start_span, false, AstClassExtensibility::Open)
+}
+
+/// Parses a class declaration that starts with modifiers (`abstract`, and one of
+/// `sealed` or `final`), assuming that `first_modifier` has already been
+/// consumed.
+pub fn parse_modified_class<'a>(
+    lxr: &mut LexerStruct<'a>,
+    first_modifier: LexerToken<'a>,
+) -> Result<ClassDecAstNode<'a>, ParseError<'a>> {
+    let start_span = lxr.span().unwrap().start;
+
+    let mut is_abstract = false;
+    let mut extensibility = AstClassExtensibility::Open;
+
+    let mut token = Some(first_modifier);
+    loop {
+        match (token, &extensibility) {
+            (Some(LexerToken::Abstract), _) if !is_abstract => is_abstract = true,
+            (Some(LexerToken::Sealed), AstClassExtensibility::Open) => {
+                extensibility = AstClassExtensibility::Sealed(lxr.span().unwrap(), Vec::new())
+            }
+            (Some(LexerToken::Final), AstClassExtensibility::Open) => {
+                extensibility = AstClassExtensibility::Final(lxr.span().unwrap())
+            }
+            (Some(LexerToken::Class), _) => break,
+            (invalid_value, _) => {
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "`class` (to declare the modified class)",
+                        "`abstract`, `sealed`, or `final` (to add another modifier to the class)",
+                    ],
+                    true,
+                ))
+            }
+        }
+
+        flush_comments(lxr);
+        token = lxr.next();
+    }
+
+    if let (true, AstClassExtensibility::Final(final_span)) = (is_abstract, &extensibility) {
+        return Err(ParseError::unexpected_token_error(
+            &lxr.source()[final_span.clone()],
+            final_span.clone(),
+            &["`sealed` (abstract classes have to be extended, so they can't be final)"],
+            true,
+        ));
+    }
+
+    parse_class_rest(lxr, start_span, is_abstract, extensibility)
+}
+
+/// Parses everything in a class declaration after the `class` keyword.
+fn parse_class_rest<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_span: usize,
+    is_abstract: bool,
+    mut extensibility: AstClassExtensibility<'a>,
+) -> Result<ClassDecAstNode<'a>, ParseError<'a>> {
     let class_type = parse_name_and_generics(lxr)?;
 
     let extends = parse_extends(lxr)?;
     let implements = parse_implements(lxr)?;
 
+    if let AstClassExtensibility::Sealed(_, permits) = &mut extensibility {
+        *permits = parse_permits(lxr)?;
+    }
+
+    let where_clause = parse_where_clause(lxr)?;
+
     expect_token(
         lxr,
         LexerToken::LeftCurlyBrace,
         &["`{` (to open the body of the class)"],
     )?;
 
-    let fields_and_final_error = parse_fields_until_none_are_left(lxr)?;
+    let fields_and_final_error = parse_fields_until_none_are_left(lxr, parse_class_field)?;
     let fields = fields_and_final_error.0;
 
-    let methods_and_final_error = match parse_methods_until_none_are_found(lxr) {
+    let members_and_final_error = match parse_members_until_none_are_found(lxr, true) {
         Ok(thing) => thing,
+        // Whichever of the two errors got further is the only thing that could
+        // have been meant there, so it's the only one reported.
+        Err(err) if err.span.start > fields_and_final_error.1.span.start => return Err(err),
+        Err(err) if err.span.start < fields_and_final_error.1.span.start => {
+            return Err(ParseError {
+                fatal: true,
+                ..fields_and_final_error.1
+            })
+        }
         Err(err) => {
             return Err(ParseError {
                 fatal: true,
@@ -34,22 +113,30 @@ pub fn parse_class<'a>(lxr: &mut LexerStruct<'a>) -> Result<ClassDecAstNode<'a>,
             })
         }
     };
-    let methods = methods_and_final_error.0;
+    let members = members_and_final_error.0;
 
     if let Err(err) = expect_token(
         lxr,
         LexerToken::RightCurlyBrace,
         &["`}` (to close the body of the class)"],
     ) {
-        return Err(combine_parse_errors(methods_and_final_error.1, err));
+        return Err(combine_parse_errors(members_and_final_error.1, err));
     }
 
     Ok(ClassDecAstNode {
         span: start_span..lxr.span().unwrap().end,
+        attributes: Vec::new(),
+        docs: Vec::new(),
+        is_abstract,
+        extensibility,
         class_type,
         extends,
         implements,
+        where_clause,
         fields,
-        methods,
+        statics: members.statics,
+        consts: members.consts,
+        constructors: members.constructors,
+        methods: members.methods,
     })
 }
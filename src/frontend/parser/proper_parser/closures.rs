@@ -0,0 +1,124 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::expressions::{AstClosureBody, AstExpression, ClosureAstNode, ClosureParamAstNode};
+use super::expressions::{parse_block_expr, parse_expression};
+use super::methods::parse_return_type;
+use super::parse_error::ParseError;
+use super::types::parse_type;
+use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
+
+/// Parses a closure (like `fun(a, b -> i32) => a + b`), assuming that the `fun`
+/// keyword has already been consumed.
+pub fn parse_closure<'a>(lxr: &mut LexerStruct<'a>) -> Result<AstExpression<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let params = parse_closure_params(lxr)?;
+
+    let return_type = parse_return_type(lxr)?;
+
+    flush_comments(lxr);
+
+    let body = match lxr.peek() {
+        Some(LexerToken::ThiccArrow) => {
+            lxr.next();
+            AstClosureBody::Expression(Box::new(parse_expression(lxr)?))
+        }
+        Some(LexerToken::LeftCurlyBrace) => AstClosureBody::Block(parse_block_expr(lxr)?),
+        invalid_value => {
+            lxr.next();
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &[
+                    "`=>` (to give the closure an expression as its body)",
+                    "`{` (to give the closure a block as its body)",
+                ],
+                true,
+            ));
+        }
+    };
+
+    let span = start_idx..lxr.span().unwrap().end;
+
+    Ok(AstExpression::Closure(
+        span.clone(),
+        Box::new(ClosureAstNode {
+            span,
+            params,
+            return_type,
+            body,
+        }),
+    ))
+}
+
+/// Parses the parameters of a closure, each of which is a name with an optional
+/// type (given with `->`).
+fn parse_closure_params<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<Vec<ClosureParamAstNode<'a>>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::LeftParenthesis,
+        &["`(` (to begin the closure's parameters)"],
+    )?;
+
+    let mut params = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        let name = match lxr.next() {
+            Some(LexerToken::Identifier(name)) => name,
+            Some(LexerToken::RightParenthesis) => break,
+            invalid_value => {
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "identifier (to define a new parameter)",
+                        "`)` (to end the closure's parameters)",
+                    ],
+                    true,
+                ))
+            }
+        };
+        let start_idx = lxr.span().unwrap().start;
+
+        flush_comments(lxr);
+
+        let param_type = if let Some(LexerToken::ThinArrow) = lxr.peek() {
+            lxr.next();
+            Some(parse_type(lxr, None)?)
+        } else {
+            None
+        };
+
+        params.push(ClosureParamAstNode {
+            span: start_idx..lxr.span().unwrap().end,
+            name,
+            param_type,
+        });
+
+        flush_comments(lxr);
+
+        match lxr.next() {
+            Some(LexerToken::Comma) => (),
+            Some(LexerToken::RightParenthesis) => break,
+            invalid_value => {
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "`,` (to signal the next parameter)",
+                        "`)` (to end the closure's parameters)",
+                    ],
+                    true,
+                ))
+            }
+        }
+    }
+
+    Ok(params)
+}
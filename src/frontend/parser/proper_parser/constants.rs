@@ -0,0 +1,110 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::attributes::AttributeList;
+use super::ast::constants::{AssociatedConstAstNode, ConstDecAstNode};
+use super::ast::expressions::AstVariableKind;
+use super::expressions::parse_expression;
+use super::parse_error::ParseError;
+use super::statements::parse_variable_declaration_rest;
+use super::types::parse_type;
+use super::utility_things::{
+    call_error, expect_semicolon, expect_token, flush_comments, LexerStruct,
+};
+
+/// Parses a module-level constant declaration, assuming that the `const`
+/// keyword has already been consumed.
+pub fn parse_const_dec<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<ConstDecAstNode<'a>, ParseError<'a>> {
+    let declaration = parse_variable_declaration_rest(lxr, AstVariableKind::Const)?;
+
+    let value = match declaration.value {
+        Some(value) => value,
+        None => {
+            return Err(ParseError::unexpected_token_error(
+                &lxr.source()[declaration.span.clone()],
+                declaration.span,
+                &["`=` (to give the constant a value)"],
+                true,
+            ))
+        }
+    };
+
+    Ok(ConstDecAstNode {
+        span: declaration.span,
+        attributes: Vec::new(),
+        docs: Vec::new(),
+        pattern: declaration.pattern,
+        const_type: declaration.var_type,
+        value,
+    })
+}
+
+/// Parses a constant in the body of a struct, class, or enum, assuming that
+/// everything up to and including the `const` keyword has already been
+/// consumed. `start_idx` is where the constant (including its attributes)
+/// begins.
+pub fn parse_associated_const_rest<'a, PublicityEnum>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
+    publicity: PublicityEnum,
+) -> Result<AssociatedConstAstNode<'a, PublicityEnum>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::Identifier(""),
+        &["identifier (to name the constant)"],
+    )?;
+    let name = lxr.slice().unwrap();
+
+    flush_comments(lxr);
+
+    // Constants are annotated like variables, so a `->` (which fields use) gets
+    // a more specific error.
+    match lxr.next() {
+        Some(LexerToken::Colon) => (),
+        invalid_value @ Some(LexerToken::ThinArrow) => {
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`:` instead of `->` (to denote the type of the constant)"],
+                true,
+            ))
+        }
+        invalid_value => {
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`:` (to denote the type of the constant)"],
+                true,
+            ))
+        }
+    }
+
+    let const_type = parse_type(lxr, None)?;
+
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::Assign,
+        &["`=` (to give the constant a value)"],
+    )?;
+
+    let value = parse_expression(lxr)?;
+
+    flush_comments(lxr);
+
+    expect_semicolon(lxr)?;
+
+    Ok(AssociatedConstAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        attributes,
+        publicity,
+        name,
+        const_type,
+        value,
+    })
+}
@@ -0,0 +1,122 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::attributes::AttributeList;
+use super::ast::methods::{ConstructorAstNode, SuperCallAstNode};
+use super::expressions::{parse_block_expr, parse_expression_list};
+use super::methods::{parse_method_args, reject_this_args};
+use super::parse_error::ParseError;
+use super::types::{parse_type, split_closing_angle_bracket};
+use super::utility_things::{expect_token, flush_comments, LexerStruct};
+
+/// Parses a constructor, assuming that everything up to and including the
+/// `constructor` keyword has already been consumed. `start_idx` is where the
+/// constructor (including its attributes) begins.
+pub fn parse_constructor_rest<'a, PublicityEnum>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
+    publicity: PublicityEnum,
+) -> Result<ConstructorAstNode<'a, PublicityEnum>, ParseError<'a>> {
+    let args = parse_method_args(lxr)?;
+
+    reject_this_args(
+        lxr,
+        &args,
+        &["identifier (constructors always take `mut this`, so it isn't written out)"],
+    )?;
+
+    flush_comments(lxr);
+
+    let super_calls = if let Some(LexerToken::Colon) = lxr.peek() {
+        lxr.next();
+        parse_super_calls(lxr)?
+    } else {
+        Vec::new()
+    };
+
+    let body = parse_block_expr(lxr)?;
+
+    Ok(ConstructorAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        attributes,
+        publicity,
+        args,
+        super_calls,
+        body,
+    })
+}
+
+/// Parses the comma-seperated `super(...)` calls of a constructor, assuming that
+/// the `:` before them has already been consumed.
+fn parse_super_calls<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<Vec<SuperCallAstNode<'a>>, ParseError<'a>> {
+    let mut super_calls = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        expect_token(
+            lxr,
+            LexerToken::Super,
+            &["`super` (to call the constructor of a superclass)"],
+        )?;
+        let start_idx = lxr.span().unwrap().start;
+
+        flush_comments(lxr);
+
+        let parent = if let Some(LexerToken::LeftAngleBracketOrLessThan) = lxr.peek() {
+            lxr.next();
+
+            flush_comments(lxr);
+
+            let parent = parse_type(lxr, None)?;
+
+            flush_comments(lxr);
+            split_closing_angle_bracket(lxr);
+
+            expect_token(
+                lxr,
+                LexerToken::RightAngleBracketOrGreaterThan,
+                &["`>` (to finish naming the superclass)"],
+            )?;
+
+            Some(parent)
+        } else {
+            None
+        };
+
+        flush_comments(lxr);
+
+        expect_token(
+            lxr,
+            LexerToken::LeftParenthesis,
+            &["`(` (to begin the arguments of the superclass's constructor)"],
+        )?;
+
+        let args = parse_expression_list(
+            lxr,
+            LexerToken::RightParenthesis,
+            &[
+                "`,` (to signal the next argument)",
+                "`)` (to end the arguments)",
+            ],
+        )?;
+
+        super_calls.push(SuperCallAstNode {
+            span: start_idx..lxr.span().unwrap().end,
+            parent,
+            args,
+        });
+
+        flush_comments(lxr);
+
+        if let Some(LexerToken::Comma) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
+        }
+    }
+
+    Ok(super_calls)
+}
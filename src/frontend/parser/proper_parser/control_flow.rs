@@ -0,0 +1,308 @@
+use logos::Span;
+
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::expressions::{
+    AstExpression, ForAstNode, IfAstNode, LoopAstNode, MatchArmAstNode, MatchAstNode, WhileAstNode,
+};
+use super::expressions::{parse_block_expr, parse_expression};
+use super::parse_error::ParseError;
+use super::patterns::{parse_binding_pattern, parse_pattern};
+use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
+
+type AstExpressionResult<'a> = Result<AstExpression<'a>, ParseError<'a>>;
+
+/// Parses an `if` expression (and any `else if`/`else` branches following it),
+/// assuming that the `if` keyword has already been consumed.
+pub fn parse_if_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let condition = parse_expression(lxr)?;
+
+    let then_block = parse_block_expr(lxr)?;
+
+    flush_comments(lxr);
+
+    let else_branch = if let Some(LexerToken::Else) = lxr.peek() {
+        lxr.next();
+
+        flush_comments(lxr);
+
+        match lxr.peek() {
+            Some(LexerToken::If) => {
+                lxr.next();
+                Some(Box::new(parse_if_expression(lxr)?))
+            }
+            Some(LexerToken::LeftCurlyBrace) => {
+                let else_block = parse_block_expr(lxr)?;
+                Some(Box::new(AstExpression::Block(
+                    else_block.span.clone(),
+                    else_block,
+                )))
+            }
+            invalid_value => {
+                lxr.next();
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "`if` (to continue the `else if` chain)",
+                        "`{` (to open the `else` block)",
+                    ],
+                    true,
+                ));
+            }
+        }
+    } else {
+        None
+    };
+
+    let span = start_idx..lxr.span().unwrap().end;
+
+    Ok(AstExpression::If(
+        span.clone(),
+        IfAstNode {
+            span,
+            condition: Box::new(condition),
+            then_block,
+            else_branch,
+        },
+    ))
+}
+
+/// Parses a loop that has been given a label (like `outer: loop { ... }`),
+/// assuming that the label has already been consumed and that the next token is
+/// the `:`.
+pub fn parse_labelled_loop<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let start_idx = lxr.span().unwrap().start;
+    let label = Some(lxr.slice().unwrap());
+
+    expect_token(lxr, LexerToken::Colon, &["`:` (to label the loop)"])?;
+
+    flush_comments(lxr);
+
+    match lxr.next() {
+        Some(LexerToken::While) => parse_while_expression(lxr, start_idx, label),
+        Some(LexerToken::Loop) => parse_loop_expression(lxr, start_idx, label),
+        Some(LexerToken::For) => parse_for_expression(lxr, start_idx, label),
+        invalid_value => Err(call_error(
+            lxr,
+            invalid_value,
+            &["`while`, `loop`, or `for` (to follow the loop label)"],
+            true,
+        )),
+    }
+}
+
+/// Parses a `while` loop, assuming that the `while` keyword has already been
+/// consumed.
+pub fn parse_while_expression<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    label: Option<&'a str>,
+) -> AstExpressionResult<'a> {
+    let condition = parse_expression(lxr)?;
+
+    let body = parse_block_expr(lxr)?;
+
+    let span = start_idx..lxr.span().unwrap().end;
+
+    Ok(AstExpression::While(
+        span.clone(),
+        WhileAstNode {
+            span,
+            label,
+            condition: Box::new(condition),
+            body,
+        },
+    ))
+}
+
+/// Parses a `loop`, assuming that the `loop` keyword has already been consumed.
+pub fn parse_loop_expression<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    label: Option<&'a str>,
+) -> AstExpressionResult<'a> {
+    let body = parse_block_expr(lxr)?;
+
+    let span = start_idx..lxr.span().unwrap().end;
+
+    Ok(AstExpression::Loop(
+        span.clone(),
+        LoopAstNode { span, label, body },
+    ))
+}
+
+/// Parses a `for` loop, assuming that the `for` keyword has already been
+/// consumed.
+pub fn parse_for_expression<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    label: Option<&'a str>,
+) -> AstExpressionResult<'a> {
+    let pattern = parse_binding_pattern(lxr, true)?;
+
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::In,
+        &["`in` (to give the value to iterate over)"],
+    )?;
+
+    let iterable = parse_expression(lxr)?;
+
+    let body = parse_block_expr(lxr)?;
+
+    let span = start_idx..lxr.span().unwrap().end;
+
+    Ok(AstExpression::For(
+        span.clone(),
+        ForAstNode {
+            span,
+            label,
+            pattern,
+            iterable: Box::new(iterable),
+            body,
+        },
+    ))
+}
+
+/// Parses a `match` expression, assuming that the `match` keyword has already
+/// been consumed.
+pub fn parse_match_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let scrutinee = parse_expression(lxr)?;
+
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::LeftCurlyBrace,
+        &["`{` (to open the match arms)"],
+    )?;
+
+    let mut arms = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::RightCurlyBrace) = lxr.peek() {
+            lxr.next();
+            break;
+        }
+
+        let arm = parse_match_arm(lxr)?;
+        let body_is_block_like = arm.body.is_block_like();
+        arms.push(arm);
+
+        flush_comments(lxr);
+
+        // Arms whose bodies end in a block don't need a comma after them.
+        match lxr.peek() {
+            Some(LexerToken::Comma) => {
+                lxr.next();
+            }
+            Some(LexerToken::RightCurlyBrace) => (),
+            _ if body_is_block_like => (),
+            invalid_value => {
+                lxr.next();
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "`,` (to signal the next match arm)",
+                        "`}` (to close the match arms)",
+                    ],
+                    true,
+                ));
+            }
+        }
+    }
+
+    let span = start_idx..lxr.span().unwrap().end;
+
+    Ok(AstExpression::Match(
+        span.clone(),
+        MatchAstNode {
+            span,
+            scrutinee: Box::new(scrutinee),
+            arms,
+        },
+    ))
+}
+
+fn parse_match_arm<'a>(lxr: &mut LexerStruct<'a>) -> Result<MatchArmAstNode<'a>, ParseError<'a>> {
+    let pattern = parse_pattern(lxr)?;
+
+    flush_comments(lxr);
+
+    let guard = if let Some(LexerToken::If) = lxr.peek() {
+        lxr.next();
+        Some(parse_expression(lxr)?)
+    } else {
+        None
+    };
+
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::ThiccArrow,
+        &["`=>` (to give the body of the match arm)"],
+    )?;
+
+    let body = parse_expression(lxr)?;
+
+    Ok(MatchArmAstNode {
+        span: pattern.get_span().start..body.get_span().end,
+        pattern,
+        guard,
+        body,
+    })
+}
+
+/// Parses the optional label after a `break` or `continue` (whichever was just
+/// consumed), returning the span of the whole jump along with the label.
+pub fn parse_jump_label<'a>(lxr: &mut LexerStruct<'a>) -> (Span, Option<&'a str>) {
+    let keyword_span = lxr.span().unwrap();
+
+    flush_comments(lxr);
+
+    if let Some(LexerToken::Identifier(label)) = lxr.peek() {
+        lxr.next();
+        (keyword_span.start..lxr.span().unwrap().end, Some(label))
+    } else {
+        (keyword_span, None)
+    }
+}
+
+/// Parses the optional value after a `return` or `yield` (whichever was just
+/// consumed), returning the span of the whole expression along with the value.
+pub fn parse_jump_value<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<(Span, Option<Box<AstExpression<'a>>>), ParseError<'a>> {
+    let keyword_span = lxr.span().unwrap();
+
+    flush_comments(lxr);
+
+    match lxr.peek() {
+        None
+        | Some(
+            LexerToken::Semicolon
+            | LexerToken::Comma
+            | LexerToken::RightCurlyBrace
+            | LexerToken::RightParenthesis
+            | LexerToken::RightSquareBracket,
+        ) => Ok((keyword_span, None)),
+        Some(_) => {
+            let value = parse_expression(lxr)?;
+            Ok((
+                keyword_span.start..value.get_span().end,
+                Some(Box::new(value)),
+            ))
+        }
+    }
+}
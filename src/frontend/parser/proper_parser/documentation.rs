@@ -0,0 +1,102 @@
+use logos::Span;
+
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::attributes::AttributeList;
+use super::ast::documentation::Documented;
+use super::attributes::parse_attributes;
+use super::parse_error::ParseError;
+use super::utility_things::LexerStruct;
+
+/// The documentation comments in front of an item that hasn't been parsed yet.
+pub struct DocComments<'a> {
+    start_idx: Option<usize>,
+    docs: Vec<&'a str>,
+}
+
+impl<'a> DocComments<'a> {
+    /// Wraps `node` (which spans `node_span`) with the documentation comments,
+    /// extending its span to cover them.
+    pub fn attach<T>(self, node: T, node_span: Span) -> Documented<'a, T> {
+        Documented {
+            span: self
+                .start_idx
+                .map_or(node_span.start, |start_idx| start_idx.min(node_span.start))
+                ..node_span.end,
+            docs: self.docs,
+            node,
+        }
+    }
+
+    fn append(&mut self, mut other: DocComments<'a>) {
+        self.start_idx = self.start_idx.or(other.start_idx);
+        self.docs.append(&mut other.docs);
+    }
+}
+
+/// Consumes every comment up to the next token that isn't one, keeping the
+/// documentation comments and throwing away the ordinary ones.
+pub fn parse_doc_comments<'a>(lxr: &mut LexerStruct<'a>) -> DocComments<'a> {
+    let mut doc_comments = DocComments {
+        start_idx: None,
+        docs: Vec::new(),
+    };
+
+    loop {
+        match lxr.peek() {
+            Some(LexerToken::Comment) => {
+                lxr.next();
+            }
+            Some(LexerToken::DocComment) => {
+                lxr.next();
+                doc_comments
+                    .start_idx
+                    .get_or_insert(lxr.span().unwrap().start);
+                doc_comments.docs.push(lxr.slice().unwrap());
+            }
+            _ => break,
+        }
+    }
+
+    doc_comments
+}
+
+/// Parses the documentation comments and attributes in front of an item, which
+/// can be written in either order (so `#[inline]` can come before or after
+/// `/// Docs`). The index that the item (including its attributes) begins at is
+/// returned along with them.
+pub fn parse_docs_and_attributes<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<(DocComments<'a>, usize, AttributeList<'a>), ParseError<'a>> {
+    let mut doc_comments = parse_doc_comments(lxr);
+
+    let start_idx = lxr.peek_span().unwrap_or(usize::MAX..usize::MAX).start;
+
+    let mut attributes = Vec::new();
+
+    loop {
+        let mut new_attributes = parse_attributes(lxr)?;
+        if new_attributes.is_empty() {
+            break;
+        }
+
+        attributes.append(&mut new_attributes);
+        doc_comments.append(parse_doc_comments(lxr));
+    }
+
+    Ok((doc_comments, start_idx, attributes))
+}
+
+/// Parses an item with `parse_item`, along with the documentation comments and
+/// attributes in front of it. `parse_item` is given the index that the item
+/// (including its attributes) begins at, and the attributes.
+pub fn parse_documented<'a, T>(
+    lxr: &mut LexerStruct<'a>,
+    parse_item: impl FnOnce(&mut LexerStruct<'a>, usize, AttributeList<'a>) -> Result<T, ParseError<'a>>,
+) -> Result<Documented<'a, T>, ParseError<'a>> {
+    let (doc_comments, start_idx, attributes) = parse_docs_and_attributes(lxr)?;
+
+    let node = parse_item(lxr, start_idx, attributes)?;
+
+    Ok(doc_comments.attach(node, start_idx..lxr.span().unwrap().end))
+}
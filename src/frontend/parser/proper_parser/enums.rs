@@ -1,179 +1,253 @@
-use super::ast::enums::{EnumCaseAstNode, EnumDecAstNode};
-
-use super::methods::parse_methods_until_none_are_found;
-use super::parse_error::ParseError;
-use super::types::{parse_name_and_generics, parse_type};
+use super::ast::attributes::AttributeList;
+use super::ast::enums::{AstEnumCasePayload, CaseList, EnumCaseAstNode, EnumDecAstNode};
+
+use super::documentation::parse_documented;
+use super::expressions::parse_expression;
+use super::extends_implements::parse_implements;
+use super::fields::{parse_field, parse_fields_until_none_are_left};
+use super::members::parse_members_until_none_are_found;
+use super::parse_error::{combine_parse_errors, ParseError};
+use super::types::{parse_name_and_generics, parse_type, parse_where_clause};
 use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
 
 use super::super::lexer::logos_lexer::LexerToken;
 
+/// Parses an enum case, whose documentation comments and attributes have
+/// already been parsed by `parse_documented`.
 pub fn parse_enum_case<'a>(
     lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
 ) -> Result<EnumCaseAstNode<'a>, ParseError<'a>> {
     flush_comments(lxr);
 
     expect_token(
         lxr,
         LexerToken::Identifier(""),
-        &["identifier (to name the enum case)"],
-    )?;
+        &["identifier (to define a new enum case)"],
+    )
+    .map_err(|err| ParseError {
+        fatal: false,
+        ..err
+    })?;
     let case_name = lxr.slice().unwrap();
-    let start_idx = lxr.span().unwrap().start;
 
     flush_comments(lxr);
 
-    let case_args = if let Some(LexerToken::LeftParenthesis) = lxr.peek() {
-        expect_token(
-            lxr,
-            LexerToken::LeftParenthesis,
-            &["`(` (to begin description of the fields of the enum case)"],
-        )?;
-
-        let mut case_args = Vec::new();
-
-        const CLOSING_PAREN_STRING: &str =
-            "`)` (to end the description of the fields of the enum case)";
-
-        loop {
-            match lxr.peek() {
-                Some(LexerToken::Identifier(_)) => case_args.push(parse_type(lxr, None)?),
-                Some(LexerToken::RightParenthesis) => break,
-                invalid_value => {
-                    lxr.next();
-                    return Err(call_error(
-                        lxr,
-                        invalid_value,
-                        &["identifier (to start a type)", CLOSING_PAREN_STRING],
-                        true,
-                    ));
-                }
-            }
+    let payload = match lxr.peek() {
+        Some(LexerToken::LeftParenthesis) => parse_tuple_payload(lxr)?,
+        Some(LexerToken::LeftCurlyBrace) => parse_struct_payload(lxr)?,
+        _ => AstEnumCasePayload::Unit,
+    };
 
-            match lxr.peek() {
-                Some(LexerToken::Comma) => {
-                    lxr.next().unwrap();
-                }
-                Some(LexerToken::RightParenthesis) => break,
-                invalid_value => {
-                    lxr.next().unwrap();
-                    return Err(call_error(
-                        lxr,
-                        invalid_value,
-                        &["`,` (to signal the next type)", CLOSING_PAREN_STRING],
-                        true,
-                    ));
-                }
-            }
-        }
+    flush_comments(lxr);
+
+    let discriminant = match (&payload, lxr.peek()) {
+        (AstEnumCasePayload::Unit, Some(LexerToken::Assign)) => {
+            lxr.next();
 
-        expect_token(lxr, LexerToken::RightParenthesis, &[CLOSING_PAREN_STRING])?;
+            let discriminant = parse_expression(lxr)?;
+
+            if !discriminant.is_constant() {
+                let discriminant_span = discriminant.get_span();
+                return Err(ParseError::unexpected_token_error(
+                    &lxr.source()[discriminant_span.clone()],
+                    discriminant_span,
+                    &["constant expression (to give the enum case a discriminant)"],
+                    true,
+                ));
+            }
 
-        case_args
-    } else {
-        Vec::new()
+            Some(discriminant)
+        }
+        _ => None,
     };
 
     Ok(EnumCaseAstNode {
         span: start_idx..lxr.span().unwrap().end,
+        attributes,
         case_name,
-        case_args,
+        payload,
+        discriminant,
     })
 }
 
-pub fn parse_enum_dec<'a>(lxr: &mut LexerStruct<'a>) -> Result<EnumDecAstNode<'a>, ParseError<'a>> {
+fn parse_tuple_payload<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AstEnumCasePayload<'a>, ParseError<'a>> {
+    expect_token(
+        lxr,
+        LexerToken::LeftParenthesis,
+        &["`(` (to begin description of the fields of the enum case)"],
+    )?;
     let start_idx = lxr.span().unwrap().start;
 
-    flush_comments(lxr);
-
-    let enum_type = parse_name_and_generics(lxr)?;
-
-    flush_comments(lxr);
-
-    let implements = match lxr.next() {
-        Some(LexerToken::Implements) => {
-            let implement_constraint = parse_type(lxr, None)?;
+    let mut case_args = Vec::new();
 
-            expect_token(
-                lxr,
-                LexerToken::LeftCurlyBrace,
-                &["`{` (to begin the body of the enum)"],
-            )?;
-
-            Some(implement_constraint)
-        }
-        Some(LexerToken::LeftCurlyBrace) => None,
-        invalid_value => {
-            return Err(call_error(
-                lxr,
-                invalid_value,
-                &[
-                    "`implements` (to signal which traits the enum implements)",
-                    "`{` (to begin the body of the enum)",
-                ],
-                true,
-            ))
-        }
-    };
-
-    let mut cases = Vec::new();
+    const CLOSING_PAREN_STRING: &str =
+        "`)` (to end the description of the fields of the enum case)";
 
     loop {
-        flush_comments(lxr);
-
         match lxr.peek() {
-            Some(
-                LexerToken::Public
-                | LexerToken::Private
-                | LexerToken::LeftAngleBracketOrLessThan
-                | LexerToken::RightCurlyBrace,
-            ) => break,
-            Some(LexerToken::Identifier(_)) => cases.push(parse_enum_case(lxr)?),
-            invalid_value => {
-                return Err(call_error(
-                    lxr,
-                    invalid_value,
-                    &[
-                        "identifier (to define a new enum case)",
-                        "`pub` or `priv` (to define a new method and its publicity)",
-                        "`<` (to start a new constraint block)",
-                    ],
-                    true,
-                ))
-            }
+            Some(LexerToken::RightParenthesis) => break,
+            _ => case_args.push(parse_type(lxr, None)?),
         }
 
-        flush_comments(lxr);
-
         match lxr.peek() {
             Some(LexerToken::Comma) => {
                 lxr.next().unwrap();
             }
-            Some(LexerToken::RightCurlyBrace) => break,
+            Some(LexerToken::RightParenthesis) => break,
             invalid_value => {
+                lxr.next().unwrap();
                 return Err(call_error(
                     lxr,
                     invalid_value,
-                    &[
-                        "`,` (to signal the next case or first method of the enum)",
-                        "`}` (to close the struct)",
-                    ],
+                    &["`,` (to signal the next type)", CLOSING_PAREN_STRING],
                     true,
-                ))
+                ));
             }
         }
     }
 
-    let methods_tuple = parse_methods_until_none_are_found(lxr)?;
-    if methods_tuple.1.fatal {
-        Err(methods_tuple.1)
-    } else {
-        let methods = methods_tuple.0;
-        Ok(EnumDecAstNode {
-            span: start_idx..lxr.span().unwrap().end,
-            enum_type,
-            implements,
-            cases,
-            methods,
-        })
+    expect_token(lxr, LexerToken::RightParenthesis, &[CLOSING_PAREN_STRING])?;
+
+    Ok(AstEnumCasePayload::Tuple(
+        start_idx..lxr.span().unwrap().end,
+        case_args,
+    ))
+}
+
+fn parse_struct_payload<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AstEnumCasePayload<'a>, ParseError<'a>> {
+    expect_token(
+        lxr,
+        LexerToken::LeftCurlyBrace,
+        &["`{` (to begin the named fields of the enum case)"],
+    )?;
+    let start_idx = lxr.span().unwrap().start;
+
+    let fields_and_final_error = parse_fields_until_none_are_left(lxr, parse_field)?;
+
+    flush_comments(lxr);
+
+    if let Err(err) = expect_token(
+        lxr,
+        LexerToken::RightCurlyBrace,
+        &["`}` (to end the named fields of the enum case)"],
+    ) {
+        return Err(combine_parse_errors(fields_and_final_error.1, err));
+    }
+
+    Ok(AstEnumCasePayload::Struct(
+        start_idx..lxr.span().unwrap().end,
+        fields_and_final_error.0,
+    ))
+}
+
+/// Parses comma-seperated (and possibly documented) enum cases until one can't
+/// be parsed, returning them along with the (non-fatal) error that ended the
+/// list.
+pub fn parse_enum_cases_until_none_are_left<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<(CaseList<'a>, ParseError<'a>), ParseError<'a>> {
+    let mut cases = Vec::new();
+
+    let first_error = loop {
+        let saved_position = lxr.save_position();
+
+        match parse_documented(lxr, parse_enum_case) {
+            Ok(case) => cases.push(case),
+            Err(err) if !err.fatal => {
+                lxr.return_to_position(saved_position);
+                break err;
+            }
+            Err(err) => return Err(err),
+        }
+
+        let saved_position = lxr.save_position();
+
+        flush_comments(lxr);
+        match expect_token(
+            lxr,
+            LexerToken::Comma,
+            &["`,` (to signal the next case or first method of the enum)"],
+        ) {
+            Ok(_) => (),
+            Err(e) => {
+                lxr.return_to_position(saved_position);
+                break ParseError { fatal: false, ..e };
+            }
+        }
+    };
+
+    Ok((cases, first_error))
+}
+
+pub fn parse_enum_dec<'a>(lxr: &mut LexerStruct<'a>) -> Result<EnumDecAstNode<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    flush_comments(lxr);
+
+    let enum_type = parse_name_and_generics(lxr)?;
+
+    flush_comments(lxr);
+
+    let implements = parse_implements(lxr)?;
+
+    let where_clause = parse_where_clause(lxr)?;
+
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::LeftCurlyBrace,
+        &["`{` (to begin the body of the enum)"],
+    )?;
+
+    let cases_and_final_error = parse_enum_cases_until_none_are_left(lxr)?;
+    let cases = cases_and_final_error.0;
+
+    let members_and_final_error = match parse_members_until_none_are_found(lxr, false) {
+        Ok(thing) => thing,
+        // Whichever of the two errors got further is the only thing that could
+        // have been meant there, so it's the only one reported.
+        Err(err) if err.span.start > cases_and_final_error.1.span.start => return Err(err),
+        Err(err) if err.span.start < cases_and_final_error.1.span.start => {
+            return Err(ParseError {
+                fatal: true,
+                ..cases_and_final_error.1
+            })
+        }
+        Err(err) => {
+            return Err(ParseError {
+                fatal: true,
+                ..combine_parse_errors(cases_and_final_error.1, err)
+            })
+        }
+    };
+    let members = members_and_final_error.0;
+
+    flush_comments(lxr);
+
+    if let Err(err) = expect_token(
+        lxr,
+        LexerToken::RightCurlyBrace,
+        &["`}` (to close the body of the enum)"],
+    ) {
+        return Err(combine_parse_errors(members_and_final_error.1, err));
     }
+
+    Ok(EnumDecAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        attributes: Vec::new(),
+        docs: Vec::new(),
+        enum_type,
+        implements,
+        where_clause,
+        cases,
+        consts: members.consts,
+        methods: members.methods,
+    })
 }
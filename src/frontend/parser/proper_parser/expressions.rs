@@ -1,9 +1,570 @@
-use super::ast::expressions::ExpressionBlockAstNode;
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::expressions::{
+    AstBinaryOperator, AstExpression, AstLiteral, AstStatement, AstUnaryOperator,
+    ExpressionBlockAstNode,
+};
+use super::closures::parse_closure;
+use super::control_flow::{
+    parse_for_expression, parse_if_expression, parse_jump_label, parse_jump_value,
+    parse_labelled_loop, parse_loop_expression, parse_match_expression, parse_while_expression,
+};
 use super::parse_error::ParseError;
-use super::utility_things::LexerStruct;
+use super::statements::{parse_assignment, parse_variable_declaration};
+use super::types::parse_type;
+use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
+
+type AstExpressionResult<'a> = Result<AstExpression<'a>, ParseError<'a>>;
 
 pub fn parse_block_expr<'a>(
-    _lxr: &mut LexerStruct<'a>,
-) -> Result<ExpressionBlockAstNode, ParseError<'a>> {
-    Ok(ExpressionBlockAstNode {})
+    lxr: &mut LexerStruct<'a>,
+) -> Result<ExpressionBlockAstNode<'a>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::LeftCurlyBrace,
+        &["`{` (to open the block)"],
+    )?;
+
+    parse_block_contents(lxr)
+}
+
+/// Parses the statements and trailing value of a block, assuming that the
+/// opening `{` has already been consumed. Consumes the closing `}`.
+pub fn parse_block_contents<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<ExpressionBlockAstNode<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let mut statements = Vec::new();
+
+    let value = loop {
+        flush_comments(lxr);
+
+        match lxr.peek() {
+            Some(LexerToken::RightCurlyBrace) => {
+                lxr.next();
+                break None;
+            }
+            // To deal with stray semicolons.
+            Some(LexerToken::Semicolon) => {
+                lxr.next();
+                continue;
+            }
+            Some(LexerToken::Let | LexerToken::Const) => {
+                let variable_declaration = parse_variable_declaration(lxr)?;
+                statements.push(AstStatement::VariableDec(
+                    variable_declaration.span.clone(),
+                    variable_declaration,
+                ));
+                continue;
+            }
+            _ => (),
+        }
+
+        // Like in Rust, a statement that starts with a block-like expression ends
+        // right after its closing `}`, so `if a { b() } (c, d);` is two
+        // statements instead of a call to the `if`.
+        let expression = if starts_block_like_expression(lxr) {
+            parse_primary_expression(lxr)?
+        } else {
+            parse_expression(lxr)?
+        };
+
+        flush_comments(lxr);
+
+        match lxr.peek() {
+            Some(LexerToken::Assign) => statements.push(parse_assignment(lxr, expression)?),
+            Some(LexerToken::Semicolon) => {
+                lxr.next();
+                statements.push(AstStatement::Expression(
+                    expression.get_span().start..lxr.span().unwrap().end,
+                    expression,
+                ));
+            }
+            Some(LexerToken::RightCurlyBrace) => {
+                lxr.next();
+                break Some(Box::new(expression));
+            }
+            _ if expression.is_block_like() => {
+                statements.push(AstStatement::Expression(expression.get_span(), expression))
+            }
+            invalid_value => {
+                lxr.next();
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "`;` (to end the statement)",
+                        "`=` (to assign a value)",
+                        "`}` (to close the block)",
+                    ],
+                    true,
+                ));
+            }
+        }
+    };
+
+    Ok(ExpressionBlockAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        statements,
+        value,
+    })
+}
+
+/// Checks whether the next expression is block-like (a block, an `if`, a loop,
+/// or a `match`) without consuming anything.
+fn starts_block_like_expression(lxr: &mut LexerStruct) -> bool {
+    match lxr.peek() {
+        Some(
+            LexerToken::LeftCurlyBrace
+            | LexerToken::If
+            | LexerToken::While
+            | LexerToken::Loop
+            | LexerToken::For
+            | LexerToken::Match,
+        ) => true,
+        // Labelled loops (like `outer: loop { ... }`)
+        Some(LexerToken::Identifier(_)) => {
+            let position = lxr.save_position();
+            lxr.next();
+            flush_comments(lxr);
+
+            let is_labelled_loop = matches!(lxr.peek(), Some(LexerToken::Colon));
+
+            lxr.return_to_position(position);
+            is_labelled_loop
+        }
+        _ => false,
+    }
+}
+
+pub fn parse_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    parse_binary_expression(lxr, 0)
+}
+
+/// Gets the operator and precedence level of a binary operator token. Higher
+/// precedence levels bind more tightly.
+///
+/// (`**` and `as` are handled separately, as they bind more tightly than any
+/// of these.)
+pub fn get_binary_operator(token: &LexerToken) -> Option<(AstBinaryOperator, u8)> {
+    let operator_info = match token {
+        LexerToken::DoubleDot => (AstBinaryOperator::Range, 0),
+
+        LexerToken::DoubleVerticalBar => (AstBinaryOperator::Or, 1),
+        LexerToken::DoubleAmpersand => (AstBinaryOperator::And, 2),
+
+        LexerToken::EqualTo => (AstBinaryOperator::EqualTo, 3),
+        LexerToken::NotEqualTo => (AstBinaryOperator::NotEqualTo, 3),
+        LexerToken::LeftAngleBracketOrLessThan => (AstBinaryOperator::LessThan, 3),
+        LexerToken::RightAngleBracketOrGreaterThan => (AstBinaryOperator::GreaterThan, 3),
+        LexerToken::LessThanOrEqualTo => (AstBinaryOperator::LessThanOrEqualTo, 3),
+        LexerToken::GreaterThanOrEqualTo => (AstBinaryOperator::GreaterThanOrEqualTo, 3),
+
+        LexerToken::VerticalBar => (AstBinaryOperator::BitOr, 4),
+        LexerToken::Caret => (AstBinaryOperator::BitXor, 5),
+        LexerToken::Ampersand => (AstBinaryOperator::BitAnd, 6),
+
+        LexerToken::DoubleLeftAngleBracket => (AstBinaryOperator::ShiftLeft, 7),
+        LexerToken::DoubleRightAngleBracket => (AstBinaryOperator::ShiftRight, 7),
+
+        LexerToken::Plus => (AstBinaryOperator::Add, 8),
+        LexerToken::Dash => (AstBinaryOperator::Subtract, 8),
+
+        LexerToken::Asterisk => (AstBinaryOperator::Multiply, 9),
+        LexerToken::ForwardSlash => (AstBinaryOperator::Divide, 9),
+        LexerToken::Percent => (AstBinaryOperator::Modulo, 9),
+
+        _ => return None,
+    };
+
+    Some(operator_info)
+}
+
+fn parse_binary_expression<'a>(
+    lxr: &mut LexerStruct<'a>,
+    min_precedence: u8,
+) -> AstExpressionResult<'a> {
+    let mut lhs = parse_cast_expression(lxr)?;
+
+    loop {
+        flush_comments(lxr);
+
+        let (operator, precedence) = match lxr.peek().as_ref().and_then(get_binary_operator) {
+            Some((operator, precedence)) if precedence >= min_precedence => (operator, precedence),
+            _ => break,
+        };
+        lxr.next();
+
+        let rhs = parse_binary_expression(lxr, precedence + 1)?;
+
+        lhs = AstExpression::Binary(
+            lhs.get_span().start..rhs.get_span().end,
+            operator,
+            Box::new(lhs),
+            Box::new(rhs),
+        );
+    }
+
+    Ok(lhs)
+}
+
+fn parse_cast_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let mut expression = parse_unary_expression(lxr)?;
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::As) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
+        }
+
+        flush_comments(lxr);
+
+        let cast_type = parse_type(lxr, None)?;
+
+        expression = AstExpression::Cast(
+            expression.get_span().start..cast_type.get_span().end,
+            Box::new(expression),
+            cast_type,
+        );
+    }
+
+    Ok(expression)
+}
+
+fn parse_unary_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    flush_comments(lxr);
+
+    let operator = match lxr.peek() {
+        Some(LexerToken::Dash) => AstUnaryOperator::Negate,
+        Some(LexerToken::Bang) => AstUnaryOperator::Not,
+        _ => return parse_power_expression(lxr),
+    };
+    lxr.next();
+    let start_idx = lxr.span().unwrap().start;
+
+    let operand = parse_unary_expression(lxr)?;
+
+    Ok(AstExpression::Unary(
+        start_idx..operand.get_span().end,
+        operator,
+        Box::new(operand),
+    ))
+}
+
+/// `**` is right-associative and binds more tightly than the unary operators on
+/// its left, so `-a ** -b` is `-(a ** (-b))`.
+fn parse_power_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let base = parse_postfix_expression(lxr)?;
+
+    flush_comments(lxr);
+
+    if let Some(LexerToken::DoubleAsterisk) = lxr.peek() {
+        lxr.next();
+
+        let exponent = parse_unary_expression(lxr)?;
+
+        Ok(AstExpression::Binary(
+            base.get_span().start..exponent.get_span().end,
+            AstBinaryOperator::Power,
+            Box::new(base),
+            Box::new(exponent),
+        ))
+    } else {
+        Ok(base)
+    }
+}
+
+fn parse_postfix_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let mut expression = parse_primary_expression(lxr)?;
+
+    loop {
+        flush_comments(lxr);
+
+        let start_idx = expression.get_span().start;
+
+        expression = match lxr.peek() {
+            // Function call
+            Some(LexerToken::LeftParenthesis) => {
+                lxr.next();
+
+                let args = parse_expression_list(
+                    lxr,
+                    LexerToken::RightParenthesis,
+                    &[
+                        "`,` (to signal the next argument)",
+                        "`)` (to end the arguments)",
+                    ],
+                )?;
+
+                AstExpression::Call(
+                    start_idx..lxr.span().unwrap().end,
+                    Box::new(expression),
+                    args,
+                )
+            }
+
+            // Macro invocation (only a bare name can be invoked as a macro)
+            Some(LexerToken::Bang) => {
+                let name = match expression {
+                    AstExpression::Name(_, name) => name,
+                    _ => break,
+                };
+                lxr.next();
+
+                flush_comments(lxr);
+
+                expect_token(
+                    lxr,
+                    LexerToken::LeftParenthesis,
+                    &["`(` (to begin the macro's arguments)"],
+                )?;
+
+                let args = parse_expression_list(
+                    lxr,
+                    LexerToken::RightParenthesis,
+                    &[
+                        "`,` (to signal the next argument)",
+                        "`)` (to end the macro's arguments)",
+                    ],
+                )?;
+
+                AstExpression::MacroCall(start_idx..lxr.span().unwrap().end, name, args)
+            }
+
+            // Member lookup (including tuple members like `.0`)
+            Some(LexerToken::MemberAccess) => {
+                lxr.next();
+
+                flush_comments(lxr);
+
+                match lxr.next() {
+                    Some(LexerToken::Identifier(_) | LexerToken::InteLiteral(_)) => (),
+                    invalid_value => {
+                        return Err(call_error(
+                            lxr,
+                            invalid_value,
+                            &["identifier or integer (to access a member)"],
+                            true,
+                        ))
+                    }
+                }
+
+                AstExpression::MemberAccess(
+                    start_idx..lxr.span().unwrap().end,
+                    Box::new(expression),
+                    lxr.slice().unwrap(),
+                )
+            }
+
+            // Subscript
+            Some(LexerToken::LeftSquareBracket) => {
+                lxr.next();
+
+                let index = parse_expression(lxr)?;
+
+                flush_comments(lxr);
+
+                expect_token(
+                    lxr,
+                    LexerToken::RightSquareBracket,
+                    &["`]` (to close the subscript)"],
+                )?;
+
+                AstExpression::Subscript(
+                    start_idx..lxr.span().unwrap().end,
+                    Box::new(expression),
+                    Box::new(index),
+                )
+            }
+
+            Some(LexerToken::Optional) => {
+                lxr.next();
+
+                AstExpression::Unary(
+                    start_idx..lxr.span().unwrap().end,
+                    AstUnaryOperator::Propagate,
+                    Box::new(expression),
+                )
+            }
+
+            _ => break,
+        };
+    }
+
+    Ok(expression)
+}
+
+/// Gets the value of a literal token, or `None` if the token isn't a literal.
+pub fn get_literal(token: &LexerToken) -> Option<AstLiteral> {
+    match token {
+        LexerToken::StriLiteral(value) => Some(AstLiteral::String(value.clone())),
+        LexerToken::CharLiteral(value) => Some(AstLiteral::Char(*value)),
+        LexerToken::InteLiteral(value) => Some(AstLiteral::Integer(*value)),
+        LexerToken::WordLiteral(value) => Some(AstLiteral::Word(*value)),
+        LexerToken::FloatLiteral(value) => Some(AstLiteral::Float(*value)),
+        LexerToken::BoolLiteral(value) => Some(AstLiteral::Bool(*value)),
+        _ => None,
+    }
+}
+
+fn parse_primary_expression<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    flush_comments(lxr);
+
+    let token = lxr.next();
+    let span = lxr.span().unwrap_or(usize::MAX..usize::MAX);
+
+    if let Some(literal) = token.as_ref().and_then(get_literal) {
+        return Ok(AstExpression::Literal(span, literal));
+    }
+
+    match token {
+        Some(LexerToken::Identifier(name)) => {
+            flush_comments(lxr);
+
+            if let Some(LexerToken::Colon) = lxr.peek() {
+                parse_labelled_loop(lxr)
+            } else {
+                Ok(AstExpression::Name(span, name))
+            }
+        }
+        Some(LexerToken::LittleThis) => Ok(AstExpression::This(span)),
+        Some(LexerToken::BigThis) => Ok(AstExpression::BigThis(span)),
+
+        Some(LexerToken::LeftParenthesis) => parse_parenthesized_or_tuple(lxr),
+
+        Some(LexerToken::LeftSquareBracket) => {
+            let elements = parse_expression_list(
+                lxr,
+                LexerToken::RightSquareBracket,
+                &[
+                    "`,` (to signal the next element)",
+                    "`]` (to close the array)",
+                ],
+            )?;
+
+            Ok(AstExpression::Array(
+                span.start..lxr.span().unwrap().end,
+                elements,
+            ))
+        }
+
+        Some(LexerToken::LeftCurlyBrace) => {
+            let block = parse_block_contents(lxr)?;
+            Ok(AstExpression::Block(block.span.clone(), block))
+        }
+
+        Some(LexerToken::If) => parse_if_expression(lxr),
+        Some(LexerToken::While) => parse_while_expression(lxr, span.start, None),
+        Some(LexerToken::Loop) => parse_loop_expression(lxr, span.start, None),
+        Some(LexerToken::For) => parse_for_expression(lxr, span.start, None),
+        Some(LexerToken::Match) => parse_match_expression(lxr),
+
+        Some(LexerToken::Function) => parse_closure(lxr),
+
+        Some(LexerToken::Break) => {
+            let (span, label) = parse_jump_label(lxr);
+            Ok(AstExpression::Break(span, label))
+        }
+        Some(LexerToken::Continue) => {
+            let (span, label) = parse_jump_label(lxr);
+            Ok(AstExpression::Continue(span, label))
+        }
+        Some(LexerToken::Return) => {
+            let (span, value) = parse_jump_value(lxr)?;
+            Ok(AstExpression::Return(span, value))
+        }
+        Some(LexerToken::Yield) => {
+            let (span, value) = parse_jump_value(lxr)?;
+            Ok(AstExpression::Yield(span, value))
+        }
+
+        invalid_value => Err(call_error(lxr, invalid_value, &["expression"], true)),
+    }
+}
+
+/// Parses either `()`, `(expr)`, or a tuple like `(expr, expr,)`, assuming that
+/// the opening `(` has already been consumed.
+fn parse_parenthesized_or_tuple<'a>(lxr: &mut LexerStruct<'a>) -> AstExpressionResult<'a> {
+    let start_idx = lxr.span().unwrap().start;
+
+    flush_comments(lxr);
+
+    if let Some(LexerToken::RightParenthesis) = lxr.peek() {
+        lxr.next();
+        return Ok(AstExpression::Tuple(
+            start_idx..lxr.span().unwrap().end,
+            Vec::new(),
+        ));
+    }
+
+    let first_expression = parse_expression(lxr)?;
+
+    flush_comments(lxr);
+
+    match lxr.next() {
+        Some(LexerToken::RightParenthesis) => Ok(AstExpression::Parenthesized(
+            start_idx..lxr.span().unwrap().end,
+            Box::new(first_expression),
+        )),
+        Some(LexerToken::Comma) => {
+            let mut elements = vec![first_expression];
+
+            elements.append(&mut parse_expression_list(
+                lxr,
+                LexerToken::RightParenthesis,
+                &[
+                    "`,` (to signal the next element)",
+                    "`)` (to close the tuple)",
+                ],
+            )?);
+
+            Ok(AstExpression::Tuple(
+                start_idx..lxr.span().unwrap().end,
+                elements,
+            ))
+        }
+        invalid_value => Err(call_error(
+            lxr,
+            invalid_value,
+            &["`,` (to create a tuple)", "`)` (to close the parentheses)"],
+            true,
+        )),
+    }
+}
+
+/// Parses a comma-seperated list of expressions (allowing a trailing comma), up
+/// to and including `closing_token`.
+pub fn parse_expression_list<'a>(
+    lxr: &mut LexerStruct<'a>,
+    closing_token: LexerToken<'a>,
+    expected_arr: &'static [&'static str],
+) -> Result<Vec<AstExpression<'a>>, ParseError<'a>> {
+    let mut expressions = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        if lxr.peek().as_ref() == Some(&closing_token) {
+            lxr.next();
+            break;
+        }
+
+        expressions.push(parse_expression(lxr)?);
+
+        flush_comments(lxr);
+
+        match lxr.next() {
+            Some(LexerToken::Comma) => (),
+            Some(token) if token == closing_token => break,
+            invalid_value => return Err(call_error(lxr, invalid_value, expected_arr, true)),
+        }
+    }
+
+    Ok(expressions)
 }
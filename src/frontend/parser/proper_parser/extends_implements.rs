@@ -1,28 +1,57 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::ast::types::AstType;
+use super::ast::types::SupertypeList;
 use super::parse_error::ParseError;
 use super::types::parse_type;
-use super::utility_things::{expect_token, LexerStruct};
+use super::utility_things::{expect_token, flush_comments, LexerStruct};
 
 pub fn parse_implements<'a>(
     lxr: &mut LexerStruct<'a>,
-) -> Result<Option<AstType<'a>>, ParseError<'a>> {
-    let position = lxr.save_position();
-    if expect_token(lxr, LexerToken::Implements, &[]).is_err() {
-        lxr.return_to_position(position);
-        return Ok(None);
-    }
+) -> Result<SupertypeList<'a>, ParseError<'a>> {
+    parse_supertype_list(lxr, LexerToken::Implements)
+}
 
-    parse_type(lxr, None).map(Some)
+pub fn parse_extends<'a>(lxr: &mut LexerStruct<'a>) -> Result<SupertypeList<'a>, ParseError<'a>> {
+    parse_supertype_list(lxr, LexerToken::Extends)
 }
 
-pub fn parse_extends<'a>(lxr: &mut LexerStruct<'a>) -> Result<Option<AstType<'a>>, ParseError<'a>> {
+/// Parses the classes that a sealed class permits to extend it.
+pub fn parse_permits<'a>(lxr: &mut LexerStruct<'a>) -> Result<SupertypeList<'a>, ParseError<'a>> {
+    parse_supertype_list(lxr, LexerToken::Permits)
+}
+
+/// Parses `keyword` followed by a list of types seperated by `,` or `&`. If the
+/// next token isn't `keyword`, nothing is consumed and the list is empty.
+fn parse_supertype_list<'a>(
+    lxr: &mut LexerStruct<'a>,
+    keyword: LexerToken<'a>,
+) -> Result<SupertypeList<'a>, ParseError<'a>> {
     let position = lxr.save_position();
-    if expect_token(lxr, LexerToken::Extends, &[]).is_err() {
+
+    flush_comments(lxr);
+
+    if expect_token(lxr, keyword, &[]).is_err() {
         lxr.return_to_position(position);
-        return Ok(None);
+        return Ok(Vec::new());
+    }
+
+    flush_comments(lxr);
+
+    let mut supertypes = vec![parse_type(lxr, None)?];
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::Comma | LexerToken::Ampersand) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
+        }
+
+        flush_comments(lxr);
+
+        supertypes.push(parse_type(lxr, None)?);
     }
 
-    parse_type(lxr, None).map(Some)
+    Ok(supertypes)
 }
@@ -1,22 +1,40 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::ast::fields::{FieldAstNode, FieldList};
+use super::ast::attributes::AttributeList;
+use super::ast::documentation::Documented;
+use super::ast::fields::{
+    ClassFieldAstNode, FieldAstNode, InheritedFieldAstNode, StaticFieldAstNode,
+};
+use super::documentation::parse_documented;
+use super::expressions::parse_expression;
 use super::publicity::ParsePublicity;
 use super::types::parse_type;
 
 use super::parse_error::ParseError;
-use super::utility_things::{expect_token, flush_comments, LexerStruct};
-
-pub fn parse_fields_until_none_are_left<'a, PublicityEnum: ParsePublicity>(
+use super::utility_things::{
+    call_peeked_error, expect_semicolon, expect_token, flush_comments, LexerStruct,
+};
+
+/// Parses comma-seperated (and possibly documented) fields with
+/// `parse_one_field` until one can't be parsed, returning them along with the
+/// (non-fatal) error that ended the list.
+///
+/// `parse_one_field` is given the index that the field begins at and its
+/// attributes, which are parsed along with its documentation comments.
+pub fn parse_fields_until_none_are_left<'a, Field>(
     lxr: &mut LexerStruct<'a>,
-) -> Result<(FieldList<'a, PublicityEnum>, ParseError<'a>), ParseError<'a>> {
+    parse_one_field: fn(
+        &mut LexerStruct<'a>,
+        usize,
+        AttributeList<'a>,
+    ) -> Result<Field, ParseError<'a>>,
+) -> Result<(Vec<Documented<'a, Field>>, ParseError<'a>), ParseError<'a>> {
     let mut fields = Vec::new();
 
     let first_error = loop {
         let saved_position = lxr.save_position();
 
-        flush_comments(lxr);
-        match parse_field(lxr) {
+        match parse_documented(lxr, parse_one_field) {
             Ok(field) => fields.push(field),
             Err(err) if !err.fatal => {
                 lxr.return_to_position(saved_position);
@@ -44,15 +62,70 @@ pub fn parse_fields_until_none_are_left<'a, PublicityEnum: ParsePublicity>(
     Ok((fields, first_error))
 }
 
+/// Parses a field that must have a type, like the fields of a struct.
 pub fn parse_field<'a, PublicityEnum: ParsePublicity>(
     lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
 ) -> Result<FieldAstNode<'a, PublicityEnum>, ParseError<'a>> {
+    let (publicity, name) = parse_field_head(lxr)?;
+
     flush_comments(lxr);
 
-    let publicity = PublicityEnum::parse_publicity(lxr)?;
-    lxr.next().unwrap();
+    if !parse_type_separator(lxr) {
+        let invalid_token = lxr.peek();
+        return Err(call_peeked_error(
+            lxr,
+            invalid_token,
+            &["`->` or `:` (to denote the type of the field)"],
+            false,
+        ));
+    }
+
+    parse_field_type(lxr, start_idx, attributes, publicity, name)
+}
+
+/// Parses a field in the body of a class, which is inherited if it leaves out
+/// its type.
+pub fn parse_class_field<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
+) -> Result<ClassFieldAstNode<'a>, ParseError<'a>> {
+    let (publicity, name) = parse_field_head(lxr)?;
+
+    flush_comments(lxr);
+
+    if parse_type_separator(lxr) {
+        let field = parse_field_type(lxr, start_idx, attributes, publicity, name)?;
+
+        Ok(ClassFieldAstNode::Declared(
+            field.span.clone(),
+            Box::new(field),
+        ))
+    } else {
+        let span = start_idx..lxr.span().unwrap().end;
+
+        Ok(ClassFieldAstNode::Inherited(
+            span.clone(),
+            InheritedFieldAstNode {
+                span,
+                attributes,
+                publicity,
+                name,
+            },
+        ))
+    }
+}
+
+/// Parses everything in a field after its attributes, up to and including its
+/// name, returning its publicity and name.
+fn parse_field_head<'a, PublicityEnum: ParsePublicity>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<(PublicityEnum, &'a str), ParseError<'a>> {
+    flush_comments(lxr);
 
-    let start_idx = lxr.span().unwrap().start;
+    let publicity = PublicityEnum::parse_publicity(lxr)?;
 
     flush_comments(lxr);
 
@@ -67,26 +140,109 @@ pub fn parse_field<'a, PublicityEnum: ParsePublicity>(
     })?;
     let name = lxr.slice().unwrap();
 
+    Ok((publicity, name))
+}
+
+/// Consumes the `->` or `:` that comes before the type of a field, returning
+/// whether there was one. (Both mean the same thing.)
+fn parse_type_separator(lxr: &mut LexerStruct) -> bool {
+    if let Some(LexerToken::ThinArrow | LexerToken::Colon) = lxr.peek() {
+        lxr.next();
+        true
+    } else {
+        false
+    }
+}
+
+/// Parses the type of a field (and its default, if it has one), assuming that the
+/// `->` or `:` before it has already been consumed.
+fn parse_field_type<'a, PublicityEnum>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
+    publicity: PublicityEnum,
+    name: &'a str,
+) -> Result<FieldAstNode<'a, PublicityEnum>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    let field_type = parse_type(lxr, None)?;
+
+    let saved_position = lxr.save_position();
+
+    flush_comments(lxr);
+
+    // (The comments are put back if there's no default, so that they aren't
+    // counted as part of the field.)
+    let default = if let Some(LexerToken::Assign) = lxr.peek() {
+        lxr.next();
+        Some(parse_expression(lxr)?)
+    } else {
+        lxr.return_to_position(saved_position);
+        None
+    };
+
+    Ok(FieldAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        attributes,
+        publicity,
+        name,
+        field_type,
+        default,
+    })
+}
+
+/// Parses a static field, assuming that everything up to and including the
+/// `static` keyword has already been consumed. `start_idx` is where the field
+/// (including its attributes) begins.
+pub fn parse_static_field_rest<'a, PublicityEnum>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
+    publicity: PublicityEnum,
+) -> Result<StaticFieldAstNode<'a, PublicityEnum>, ParseError<'a>> {
     flush_comments(lxr);
 
     expect_token(
         lxr,
-        LexerToken::ThinArrow,
-        &["`->` (to denote the type of the field)"],
-    )
-    .map_err(|err| ParseError {
-        fatal: false,
-        ..err
-    })?;
+        LexerToken::Identifier(""),
+        &["identifier (to set the name of the static field)"],
+    )?;
+    let name = lxr.slice().unwrap();
 
     flush_comments(lxr);
 
+    if !parse_type_separator(lxr) {
+        let invalid_token = lxr.peek();
+        return Err(call_peeked_error(
+            lxr,
+            invalid_token,
+            &["`->` or `:` (to denote the type of the static field)"],
+            true,
+        ));
+    }
+
     let field_type = parse_type(lxr, None)?;
 
-    Ok(FieldAstNode {
+    flush_comments(lxr);
+
+    expect_token(
+        lxr,
+        LexerToken::Assign,
+        &["`=` (to give the static field its initial value)"],
+    )?;
+
+    let value = parse_expression(lxr)?;
+
+    flush_comments(lxr);
+
+    expect_semicolon(lxr)?;
+
+    Ok(StaticFieldAstNode {
         span: start_idx..lxr.span().unwrap().end,
+        attributes,
         publicity,
         name,
         field_type,
+        value,
     })
 }
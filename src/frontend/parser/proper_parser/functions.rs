@@ -0,0 +1,42 @@
+use super::ast::functions::FunctionDecAstNode;
+use super::expressions::parse_block_expr;
+use super::methods::{parse_method_args, parse_return_type, reject_this_args};
+use super::parse_error::ParseError;
+use super::types::{parse_name_and_generics, parse_where_clause};
+use super::utility_things::LexerStruct;
+
+/// Parses a free function declaration, assuming that the `fun` keyword has
+/// already been consumed.
+pub fn parse_function_dec<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<FunctionDecAstNode<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let function_type = parse_name_and_generics(lxr)?;
+
+    let args = parse_method_args(lxr)?;
+
+    // Free functions don't have an instance to take.
+    reject_this_args(
+        lxr,
+        &args,
+        &["identifier (free functions can't take `this`)"],
+    )?;
+
+    let return_type = parse_return_type(lxr)?;
+
+    let where_clause = parse_where_clause(lxr)?;
+
+    let body = parse_block_expr(lxr)?;
+
+    Ok(FunctionDecAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        attributes: Vec::new(),
+        docs: Vec::new(),
+        function_type,
+        args,
+        return_type,
+        where_clause,
+        body,
+    })
+}
@@ -1,112 +1,333 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::patterns::parse_destructuring_pattern;
+use super::patterns::{parse_destructuring_pattern, parse_destructuring_pattern_list};
 use super::top_level::parse_top_level;
 
 use super::utility_things::{
-    expect_semicolon, expect_token, flush_comments, LexerStruct, TopLevelAstResult,
+    call_error, expect_semicolon, expect_token, flush_comments, LexerStruct, TopLevelAstResult,
 };
 
-use super::ast::imports_exports::{AstModuleLocation, ImportStatementAstNode};
+use super::ast::imports_exports::{
+    AstExportedItems, AstImportedItems, AstModuleLocation, ExportStatementAstNode,
+    ImportStatementAstNode,
+};
 use super::ast::top_level::TopLevelAstNode;
 use super::parse_error::ParseError;
 
+/// Parses an import statement, assuming that the `import` keyword has already
+/// been consumed.
 pub fn parse_import_statement<'a>(
     lxr: &mut LexerStruct<'a>,
+) -> Result<ImportStatementAstNode<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    parse_import_statement_rest(lxr, start_idx).map_err(|mut parse_err| {
+        parse_err.fatal = true;
+        parse_err
+    })
+}
+
+fn parse_import_statement_rest<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
 ) -> Result<ImportStatementAstNode<'a>, ParseError<'a>> {
     flush_comments(lxr);
 
-    match parse_destructuring_pattern(lxr) {
-        Ok(destructuring_pattern) => {
+    let (imported_items, module_location) = if let Some(LexerToken::Asterisk) = lxr.peek() {
+        lxr.next();
+        let glob_span = lxr.span().unwrap();
+
+        (AstImportedItems::Glob(glob_span), parse_from_module(lxr)?)
+    } else if is_destructuring_import(lxr) {
+        let destructuring_pattern = parse_destructuring_pattern(lxr)?;
+
+        (
+            AstImportedItems::Destructured(destructuring_pattern),
+            parse_from_module(lxr)?,
+        )
+    } else {
+        let module_location = parse_module_location(lxr)?;
+        let module_span = module_location.get_span();
+
+        flush_comments(lxr);
+
+        let alias = if let Some(LexerToken::As) = lxr.peek() {
+            lxr.next();
+
             flush_comments(lxr);
 
-            expect_token(lxr, LexerToken::From, &["`from`"])?;
+            expect_token(
+                lxr,
+                LexerToken::Identifier(""),
+                &["identifier (to rename the imported module)"],
+            )?;
+            Some(lxr.slice().unwrap())
+        } else {
+            None
+        };
+
+        let end_idx = match alias {
+            Some(_) => lxr.span().unwrap().end,
+            None => module_span.end,
+        };
+
+        (
+            AstImportedItems::Module(module_span.start..end_idx, alias),
+            module_location,
+        )
+    };
+
+    flush_comments(lxr);
+
+    expect_semicolon(lxr)?;
+
+    Ok(ImportStatementAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        imported_items,
+        module_location,
+    })
+}
 
-            let module_location = {
-                let mut module_path: AstModuleLocation;
+/// Parses the `from` and module location that follow the items being imported.
+fn parse_from_module<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AstModuleLocation<'a>, ParseError<'a>> {
+    flush_comments(lxr);
 
-                flush_comments(lxr);
+    expect_token(lxr, LexerToken::From, &["`from`"])?;
 
-                expect_token(
-                    lxr,
-                    LexerToken::Identifier(""),
-                    &["identifier (as part of module path)"],
-                )?;
+    parse_module_location(lxr)
+}
 
-                module_path = AstModuleLocation::Root(lxr.span().unwrap(), lxr.slice().unwrap());
+/// Checks (without consuming anything) whether the import starts with a
+/// destructuring pattern, which is always followed by `:` or `from`. Anything
+/// else is the path of a module that is being imported itself.
+fn is_destructuring_import(lxr: &mut LexerStruct) -> bool {
+    let position = lxr.save_position();
 
-                loop {
-                    flush_comments(lxr);
+    flush_comments(lxr);
 
-                    if let Some(LexerToken::MemberAccess) = lxr.peek() {
-                        lxr.next();
-                    } else {
-                        break;
-                    }
+    let is_destructuring = match lxr.next() {
+        Some(LexerToken::Identifier(_)) => {
+            flush_comments(lxr);
+            matches!(lxr.peek(), Some(LexerToken::Colon | LexerToken::From))
+        }
+        _ => false,
+    };
 
-                    flush_comments(lxr);
+    lxr.return_to_position(position);
 
-                    expect_token(
-                        lxr,
-                        LexerToken::Identifier(""),
-                        &["identifier (as part of module path)"],
-                    )?;
-                    let new_span = module_path.get_span().start..lxr.span().unwrap().end;
+    is_destructuring
+}
 
-                    module_path = AstModuleLocation::MemberOf(
-                        new_span,
-                        Box::new(module_path),
-                        lxr.slice().unwrap(),
-                    );
-                }
+/// Parses the location of a module, which is either a path (which can start
+/// with `self`, `super`, or `package` to be relative) or a string path to a
+/// sibling `.uck` file.
+pub fn parse_module_location<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AstModuleLocation<'a>, ParseError<'a>> {
+    flush_comments(lxr);
 
-                module_path
-            };
+    let mut module_path = match lxr.next() {
+        Some(LexerToken::StriLiteral(_)) => return parse_file_location(lxr),
+        Some(LexerToken::Super) => AstModuleLocation::Super(lxr.span().unwrap(), 1),
+        Some(LexerToken::Identifier("self")) => {
+            AstModuleLocation::CurrentModule(lxr.span().unwrap())
+        }
+        Some(LexerToken::Identifier("package")) => AstModuleLocation::Package(lxr.span().unwrap()),
+        Some(LexerToken::Identifier(name)) => AstModuleLocation::Root(lxr.span().unwrap(), name),
+        invalid_value => {
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &[
+                    "identifier (as part of module path)",
+                    "`self`, `super`, or `package` (to start a relative module path)",
+                    "string (to give the path of a sibling `.uck` file)",
+                ],
+                true,
+            ))
+        }
+    };
 
-            expect_semicolon(lxr)?;
+    loop {
+        flush_comments(lxr);
 
-            Ok(ImportStatementAstNode {
-                span: destructuring_pattern.get_span().start..lxr.span().unwrap().end,
-                destructuring_pattern,
-                module_location,
-            })
+        if let Some(LexerToken::MemberAccess) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
         }
-        Err(mut parse_err) => {
-            parse_err.fatal = true;
-            Err(parse_err)
+
+        flush_comments(lxr);
+
+        // `super`s can only be chained onto other `super`s.
+        if let AstModuleLocation::Super(span, levels) = &mut module_path {
+            if let Some(LexerToken::Super) = lxr.peek() {
+                lxr.next();
+                span.end = lxr.span().unwrap().end;
+                *levels += 1;
+                continue;
+            }
         }
+
+        expect_token(
+            lxr,
+            LexerToken::Identifier(""),
+            &["identifier (as part of module path)"],
+        )?;
+        let new_span = module_path.get_span().start..lxr.span().unwrap().end;
+
+        module_path =
+            AstModuleLocation::MemberOf(new_span, Box::new(module_path), lxr.slice().unwrap());
+    }
+
+    Ok(module_path)
+}
+
+/// Parses a string path to a `.uck` file, assuming that the string has already
+/// been consumed. Only files in the same directory can be imported this way.
+fn parse_file_location<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<AstModuleLocation<'a>, ParseError<'a>> {
+    let slice = lxr.slice().unwrap();
+    let span = lxr.span().unwrap();
+
+    // The slice still has its quotes (and possibly an `l` in front of them).
+    let path = &slice[slice.find('"').unwrap() + 1..slice.len() - 1];
+
+    let is_sibling_file =
+        path.len() > ".uck".len() && path.ends_with(".uck") && !path.contains(['/', '\\']);
+
+    if is_sibling_file {
+        Ok(AstModuleLocation::File(span, path))
+    } else {
+        Err(ParseError::unexpected_token_error(
+            slice,
+            span,
+            &["string path to a sibling `.uck` file (like `\"utils.uck\"`)"],
+            true,
+        ))
     }
 }
 
+/// Parses an export statement, assuming that the `export` keyword has already
+/// been consumed.
+///
+/// This is either a (possibly default) export of the declaration that follows,
+/// or a list of exported items (like `export { a, b: renamed };`, which can also
+/// re-export items with `from`).
 pub fn parse_export_statement<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
     let start = lxr.span().unwrap().start;
 
     flush_comments(lxr);
 
-    if let Some(LexerToken::Default) = lxr.peek() {
-        lxr.next();
-        parse_top_level(lxr)
-            .map(|top_level_statement| {
+    match lxr.peek() {
+        Some(LexerToken::Default) => {
+            lxr.next();
+            parse_exported_declaration(lxr).map(|top_level_statement| {
                 TopLevelAstNode::ExportDefault(
                     start..top_level_statement.get_span().end,
                     Box::new(top_level_statement),
                 )
             })
-            .map_err(|mut error| {
-                error.fatal = true;
-                error
-            })
-    } else {
-        parse_top_level(lxr)
-            .map(|top_level_statement| {
-                TopLevelAstNode::Export(
-                    start..top_level_statement.get_span().end,
-                    Box::new(top_level_statement),
-                )
-            })
-            .map_err(|mut error| {
+        }
+        Some(LexerToken::LeftCurlyBrace | LexerToken::Asterisk) => {
+            let export_statement = parse_export_items(lxr, start).map_err(|mut error| {
                 error.fatal = true;
                 error
-            })
+            })?;
+
+            Ok(TopLevelAstNode::ExportItems(
+                export_statement.span.clone(),
+                export_statement,
+            ))
+        }
+        _ => parse_exported_declaration(lxr).map(|top_level_statement| {
+            TopLevelAstNode::Export(
+                start..top_level_statement.get_span().end,
+                Box::new(top_level_statement),
+            )
+        }),
+    }
+}
+
+/// Parses the declaration after `export` (or `export default`).
+///
+/// Imports can't be exported this way, since re-exports are written as
+/// `export { ... } from ...;` instead.
+fn parse_exported_declaration<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
+    flush_comments(lxr);
+
+    if let Some(LexerToken::Import) = lxr.peek() {
+        lxr.next();
+        return Err(ParseError::unexpected_token_error(
+            lxr.slice().unwrap(),
+            lxr.span().unwrap(),
+            &[
+                "declaration (to export)",
+                "`{` or `*` (to re-export items, like `export { X } from other.module;`, since imports can't be exported)",
+            ],
+            true,
+        ));
     }
+
+    parse_top_level(lxr).map_err(|mut error| {
+        error.fatal = true;
+        error
+    })
+}
+
+/// Parses the rest of an export statement that lists what it exports (or that
+/// uses `*`), assuming that only the `export` keyword has been consumed.
+fn parse_export_items<'a>(
+    lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+) -> Result<ExportStatementAstNode<'a>, ParseError<'a>> {
+    let exported_items = match lxr.next() {
+        Some(LexerToken::LeftCurlyBrace) => {
+            let list_start = lxr.span().unwrap().start;
+            let exported_list = parse_destructuring_pattern_list(lxr)?;
+
+            AstExportedItems::List(list_start..lxr.span().unwrap().end, exported_list)
+        }
+        Some(LexerToken::Asterisk) => AstExportedItems::Glob(lxr.span().unwrap()),
+        invalid_value => {
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`{` or `*` (to say what is exported)"],
+                true,
+            ))
+        }
+    };
+
+    flush_comments(lxr);
+
+    let module_location = match (lxr.peek(), &exported_items) {
+        (Some(LexerToken::From), _) => {
+            lxr.next();
+            Some(parse_module_location(lxr)?)
+        }
+        (_, AstExportedItems::Glob(_)) => {
+            let invalid_value = lxr.next();
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`from` (to give the module to export everything from)"],
+                true,
+            ));
+        }
+        _ => None,
+    };
+
+    flush_comments(lxr);
+
+    expect_semicolon(lxr)?;
+
+    Ok(ExportStatementAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        exported_items,
+        module_location,
+    })
 }
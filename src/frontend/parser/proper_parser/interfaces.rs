@@ -0,0 +1,47 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::interfaces::InterfaceDecAstNode;
+use super::extends_implements::parse_extends;
+use super::methods::parse_methods_until_none_are_found;
+use super::parse_error::{combine_parse_errors, ParseError};
+use super::types::{parse_name_and_generics, parse_where_clause};
+use super::utility_things::{expect_token, LexerStruct};
+
+pub fn parse_interface<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<InterfaceDecAstNode<'a>, ParseError<'a>> {
+    let start_span = lxr.span().unwrap().start;
+
+    let interface_type = parse_name_and_generics(lxr)?;
+
+    let extends = parse_extends(lxr)?;
+
+    let where_clause = parse_where_clause(lxr)?;
+
+    expect_token(
+        lxr,
+        LexerToken::LeftCurlyBrace,
+        &["`{` (to open the body of the interface)"],
+    )?;
+
+    let methods_and_final_error = parse_methods_until_none_are_found(lxr, true)?;
+    let methods = methods_and_final_error.0;
+
+    if let Err(err) = expect_token(
+        lxr,
+        LexerToken::RightCurlyBrace,
+        &["`}` (to close the body of the interface)"],
+    ) {
+        return Err(combine_parse_errors(methods_and_final_error.1, err));
+    }
+
+    Ok(InterfaceDecAstNode {
+        span: start_span..lxr.span().unwrap().end,
+        attributes: Vec::new(),
+        docs: Vec::new(),
+        interface_type,
+        extends,
+        where_clause,
+        methods,
+    })
+}
@@ -0,0 +1,138 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::constants::{AssociatedConstAstNode, AssociatedConstList};
+use super::ast::documentation::Documented;
+use super::ast::fields::{StaticFieldAstNode, StaticFieldList};
+use super::ast::methods::{ConstructorAstNode, ConstructorList, MethodList};
+use super::constants::parse_associated_const_rest;
+use super::constructors::parse_constructor_rest;
+use super::documentation::parse_docs_and_attributes;
+use super::fields::parse_static_field_rest;
+use super::methods::parse_method_or_constraint_block;
+use super::parse_error::ParseError;
+use super::publicity::ParsePublicity;
+use super::utility_things::{call_error, flush_comments, LexerStruct};
+
+/// The members of a struct, class, or enum body that come after its fields (or
+/// cases). They can be written in any order.
+pub struct TypeBodyMembers<'a, PublicityEnum> {
+    pub consts: AssociatedConstList<'a, PublicityEnum>,
+    pub statics: StaticFieldList<'a, PublicityEnum>,
+    pub constructors: ConstructorList<'a, PublicityEnum>,
+    pub methods: MethodList<'a, PublicityEnum>,
+}
+
+enum NonMethodMember<'a, PublicityEnum> {
+    Const(Documented<'a, AssociatedConstAstNode<'a, PublicityEnum>>),
+    Static(Documented<'a, StaticFieldAstNode<'a, PublicityEnum>>),
+    Constructor(Documented<'a, ConstructorAstNode<'a, PublicityEnum>>),
+}
+
+/// Parses methods, constraint blocks, and associated constants until something
+/// that isn't one is found, returning them along with the (non-fatal) error that
+/// ended the list.
+///
+/// `is_class` allows static fields and constructors to be parsed as well.
+pub fn parse_members_until_none_are_found<'a, PublicityEnum: ParsePublicity>(
+    lxr: &mut LexerStruct<'a>,
+    is_class: bool,
+) -> Result<(TypeBodyMembers<'a, PublicityEnum>, ParseError<'a>), ParseError<'a>> {
+    let mut members = TypeBodyMembers {
+        consts: Vec::new(),
+        statics: Vec::new(),
+        constructors: Vec::new(),
+        methods: Vec::new(),
+    };
+
+    let first_error = loop {
+        let curr_spot = lxr.save_position();
+
+        match parse_non_method_member(lxr, is_class)? {
+            Some(NonMethodMember::Const(associated_const)) => members.consts.push(associated_const),
+            Some(NonMethodMember::Static(static_field)) => members.statics.push(static_field),
+            Some(NonMethodMember::Constructor(constructor)) => {
+                members.constructors.push(constructor)
+            }
+            None => {
+                lxr.return_to_position(curr_spot);
+                let curr_spot = lxr.save_position();
+
+                match parse_method_or_constraint_block(lxr, false) {
+                    Ok(method) => members.methods.push(method),
+                    Err(err) if !err.fatal => {
+                        lxr.return_to_position(curr_spot);
+                        break err;
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        if let Some(LexerToken::Comma) = lxr.peek() {
+            lxr.next();
+        }
+    };
+
+    Ok((members, first_error))
+}
+
+/// Parses an associated constant, a static field, or a constructor, returning
+/// `None` (without necessarily leaving the lexer where it was) if the member is
+/// none of those.
+fn parse_non_method_member<'a, PublicityEnum: ParsePublicity>(
+    lxr: &mut LexerStruct<'a>,
+    is_class: bool,
+) -> Result<Option<NonMethodMember<'a, PublicityEnum>>, ParseError<'a>> {
+    let (doc_comments, start_idx, attributes) = parse_docs_and_attributes(lxr)?;
+
+    let publicity = match PublicityEnum::parse_publicity(lxr) {
+        Ok(publicity) => publicity,
+        Err(_) => return Ok(None),
+    };
+
+    flush_comments(lxr);
+
+    match lxr.peek() {
+        Some(LexerToken::Const) => {
+            lxr.next();
+
+            let associated_const =
+                parse_associated_const_rest(lxr, start_idx, attributes, publicity)?;
+            let span = associated_const.span.clone();
+
+            Ok(Some(NonMethodMember::Const(
+                doc_comments.attach(associated_const, span),
+            )))
+        }
+        Some(LexerToken::Static) if is_class => {
+            lxr.next();
+
+            let static_field = parse_static_field_rest(lxr, start_idx, attributes, publicity)?;
+            let span = static_field.span.clone();
+
+            Ok(Some(NonMethodMember::Static(
+                doc_comments.attach(static_field, span),
+            )))
+        }
+        Some(LexerToken::Constructor) if is_class => {
+            lxr.next();
+
+            let constructor = parse_constructor_rest(lxr, start_idx, attributes, publicity)?;
+            let span = constructor.span.clone();
+
+            Ok(Some(NonMethodMember::Constructor(
+                doc_comments.attach(constructor, span),
+            )))
+        }
+        invalid_value @ Some(LexerToken::Static | LexerToken::Constructor) => {
+            lxr.next();
+            Err(call_error(
+                lxr,
+                invalid_value,
+                &["`fun` or `const` (only classes can have static fields or constructors)"],
+                true,
+            ))
+        }
+        _ => Ok(None),
+    }
+}
@@ -1,23 +1,35 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
+use super::ast::attributes::AttributeList;
+use super::ast::documentation::Documented;
+use super::ast::expressions::{AstBinaryOperator, AstUnaryOperator};
 use super::ast::methods::{
-    AstMethodArgument, MethodAstNode, MethodList, MethodOrConstraintAstNode,
-    PossiblyDocumentedMethodAstNode,
+    AstMethodArgument, AstOverloadableOperator, MethodAstNode, MethodList,
+    MethodOrConstraintAstNode,
 };
-use super::expressions::parse_block_expr;
+use super::ast::types::{AstType, NameAndGenericsAstNode};
+use super::documentation::{parse_doc_comments, parse_docs_and_attributes};
+use super::expressions::{get_binary_operator, parse_block_expr};
 use super::parse_error::ParseError;
 use super::publicity::ParsePublicity;
-use super::types::{parse_generics, parse_name_and_generics, parse_type};
-use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
+use super::types::{parse_generic_params, parse_name_and_generics, parse_type, parse_where_clause};
+use super::utility_things::{
+    call_error, call_peeked_error, expect_token, flush_comments, LexerStruct,
+};
 
+/// Parses methods and constraint blocks until something that isn't one is found.
+///
+/// `allow_bodiless` allows methods to be signatures ending in `;` instead of
+/// having a body (as in interfaces).
 pub fn parse_methods_until_none_are_found<'a, PublicityEnum: ParsePublicity>(
     lxr: &mut LexerStruct<'a>,
+    allow_bodiless: bool,
 ) -> Result<(MethodList<'a, PublicityEnum>, ParseError<'a>), ParseError<'a>> {
     let mut methods = Vec::new();
     let first_error = loop {
         let curr_spot = lxr.save_position();
 
-        match parse_possibly_documented_method_or_constraint_block(lxr) {
+        match parse_method_or_constraint_block(lxr, allow_bodiless) {
             Ok(method) => methods.push(method),
             Err(err) if !err.fatal => {
                 lxr.return_to_position(curr_spot);
@@ -34,48 +46,34 @@ pub fn parse_methods_until_none_are_found<'a, PublicityEnum: ParsePublicity>(
     Ok((methods, first_error))
 }
 
-pub fn parse_possibly_documented_method_or_constraint_block<'a, PublicityEnum: ParsePublicity>(
+/// Parses a (possibly documented) method or a constraint block. Documentation
+/// comments in front of a constraint block are thrown away.
+pub fn parse_method_or_constraint_block<'a, PublicityEnum: ParsePublicity>(
     lxr: &mut LexerStruct<'a>,
+    allow_bodiless: bool,
 ) -> Result<MethodOrConstraintAstNode<'a, PublicityEnum>, ParseError<'a>> {
+    let (doc_comments, start_idx, attributes) = parse_docs_and_attributes(lxr)?;
+
     match PublicityEnum::parse_publicity(lxr) {
         Ok(publicity) => {
-            lxr.next().unwrap();
-            let method = parse_method(lxr, publicity)?;
+            let method = parse_method(lxr, start_idx, attributes, publicity, allow_bodiless)?;
+            let method_span = method.span.clone();
+            let documented_method = doc_comments.attach(method, method_span);
+
             Ok(MethodOrConstraintAstNode::Method(
-                method.span.clone(),
-                PossiblyDocumentedMethodAstNode::BaseMethod(method.span.clone(), method),
+                documented_method.span.clone(),
+                Box::new(documented_method),
             ))
         }
+        // Constraint blocks can't come after attributes.
+        Err(err) if !attributes.is_empty() => Err(ParseError { fatal: true, ..err }),
         Err(_) => match lxr.peek() {
-            Some(LexerToken::Comment) => {
-                lxr.next();
-                let comment_start_idx = lxr.span().unwrap().start;
-                let comment_contents = lxr.slice().unwrap();
-
-                match parse_possibly_documented_method_or_constraint_block(lxr) {
-                    Ok(MethodOrConstraintAstNode::Method(span, method)) => {
-                        Ok(MethodOrConstraintAstNode::Method(
-                            comment_start_idx..span.end,
-                            PossiblyDocumentedMethodAstNode::DocumentedMethod(
-                                comment_start_idx..span.end,
-                                comment_contents,
-                                Box::new(method),
-                            ),
-                        ))
-                    }
-                    Ok(MethodOrConstraintAstNode::Constraint(span, generics, method_list)) => Ok(
-                        MethodOrConstraintAstNode::Constraint(span, generics, method_list),
-                    ),
-                    Err(err) => Err(err),
-                }
-            }
-
             Some(LexerToken::LeftAngleBracketOrLessThan) => {
                 lxr.next();
 
                 let start_idx = lxr.span().unwrap().start;
 
-                let constraint_generics = parse_generics(lxr, true)?;
+                let constraint_generics = parse_generic_params(lxr, false)?;
 
                 expect_token(
                     lxr,
@@ -83,7 +81,8 @@ pub fn parse_possibly_documented_method_or_constraint_block<'a, PublicityEnum: P
                     &["`{` (to start the constraint block)"],
                 )?;
 
-                let constrained_methods_result = parse_methods_until_none_are_found(lxr)?;
+                let constrained_methods_result =
+                    parse_methods_until_none_are_found(lxr, allow_bodiless)?;
 
                 if expect_token(
                     lxr,
@@ -104,7 +103,7 @@ pub fn parse_possibly_documented_method_or_constraint_block<'a, PublicityEnum: P
                 ))
             }
 
-            invalid_value => Err(call_error(
+            invalid_value => Err(call_peeked_error(
                 lxr,
                 invalid_value,
                 &[
@@ -117,40 +116,175 @@ pub fn parse_possibly_documented_method_or_constraint_block<'a, PublicityEnum: P
     }
 }
 
+/// Parses a method (starting with its `abstract` and `override` modifiers, if
+/// it has any), assuming that its attributes and publicity have already been
+/// parsed. `start_idx` is where the method (including those) begins.
 pub fn parse_method<'a, PublicityEnum>(
     lxr: &mut LexerStruct<'a>,
+    start_idx: usize,
+    attributes: AttributeList<'a>,
     publicity: PublicityEnum,
+    allow_bodiless: bool,
 ) -> Result<MethodAstNode<'a, PublicityEnum>, ParseError<'a>> {
-    let start_idx = lxr.span().unwrap().start;
+    let mut is_abstract = false;
+    let mut is_override = false;
 
-    expect_token(lxr, LexerToken::Function, &["`fun`"])?;
+    loop {
+        flush_comments(lxr);
 
-    let new_type = parse_name_and_generics(lxr)?;
+        match lxr.peek() {
+            Some(LexerToken::Abstract) if !is_abstract => is_abstract = true,
+            Some(LexerToken::Override) if !is_override => is_override = true,
+            _ => break,
+        }
 
-    let args = parse_method_args(lxr)?;
+        lxr.next();
+    }
+
+    expect_token(lxr, LexerToken::Function, &["`fun`"])?;
+
+    flush_comments(lxr);
 
-    let return_type = if let Some(LexerToken::ThinArrow) = lxr.peek() {
+    let (new_type, operator) = if let Some(LexerToken::Operator) = lxr.peek() {
         lxr.next();
-        Some(parse_type(lxr, None)?)
+        let (new_type, operator) = parse_operator_name(lxr)?;
+        (new_type, Some(operator))
     } else {
-        None
+        (parse_name_and_generics(lxr)?, None)
     };
 
-    let body = parse_block_expr(lxr)?;
+    let args = parse_method_args(lxr)?;
+
+    // `operator-` only negates if it doesn't take anything other than `this`.
+    let operator = match operator {
+        Some(AstOverloadableOperator::Binary(AstBinaryOperator::Subtract))
+            if !args
+                .iter()
+                .any(|arg| matches!(arg.node, AstMethodArgument::Regular(_, _, _))) =>
+        {
+            Some(AstOverloadableOperator::Unary(AstUnaryOperator::Negate))
+        }
+        operator => operator,
+    };
+
+    let return_type = parse_return_type(lxr)?;
+
+    let where_clause = parse_where_clause(lxr)?;
+
+    flush_comments(lxr);
+
+    let body = match lxr.peek() {
+        Some(LexerToken::Semicolon) if allow_bodiless || is_abstract => {
+            lxr.next();
+            None
+        }
+        invalid_value if is_abstract => {
+            lxr.next();
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`;` (abstract methods don't have a body)"],
+                true,
+            ));
+        }
+        _ => Some(parse_block_expr(lxr)?),
+    };
 
     Ok(MethodAstNode {
         span: start_idx..lxr.span().unwrap().end,
+        attributes,
         publicity,
+        is_abstract,
+        is_override,
+        operator,
         new_type,
         args,
         return_type,
+        where_clause,
         body,
     })
 }
 
+/// Parses the operator in the name of an operator overload (and any generic
+/// params after it), assuming that the `operator` keyword has already been
+/// consumed. The name that is returned is the operator's method name.
+fn parse_operator_name<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<(NameAndGenericsAstNode<'a>, AstOverloadableOperator), ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    flush_comments(lxr);
+
+    let token = lxr.next();
+
+    let operator = match &token {
+        Some(LexerToken::Bang) => Some(AstOverloadableOperator::Unary(AstUnaryOperator::Not)),
+        Some(LexerToken::DoubleAsterisk) => {
+            Some(AstOverloadableOperator::Binary(AstBinaryOperator::Power))
+        }
+        Some(LexerToken::LeftSquareBracket) => {
+            flush_comments(lxr);
+            expect_token(
+                lxr,
+                LexerToken::RightSquareBracket,
+                &["`]` (to finish `operator[]`)"],
+            )?;
+            Some(AstOverloadableOperator::Subscript)
+        }
+        Some(LexerToken::LeftParenthesis) => {
+            flush_comments(lxr);
+            expect_token(
+                lxr,
+                LexerToken::RightParenthesis,
+                &["`)` (to finish `operator()`)"],
+            )?;
+            Some(AstOverloadableOperator::Call)
+        }
+        Some(token) => match get_binary_operator(token) {
+            // These short-circuit, so they can't be implemented as methods.
+            Some((AstBinaryOperator::Or | AstBinaryOperator::And, _)) | None => None,
+            Some((operator, _)) => Some(AstOverloadableOperator::Binary(operator)),
+        },
+        None => None,
+    };
+
+    let operator = match operator {
+        Some(operator) => operator,
+        None => {
+            return Err(call_error(
+                lxr,
+                token,
+                &["overloadable operator (like `+`, `==`, `[]`, or `()`)"],
+                true,
+            ))
+        }
+    };
+
+    let mut end_idx = lxr.span().unwrap().end;
+
+    flush_comments(lxr);
+
+    let generic_params = if let Some(LexerToken::LeftAngleBracketOrLessThan) = lxr.peek() {
+        lxr.next();
+        let generic_params = parse_generic_params(lxr, true)?;
+        end_idx = generic_params.span.end;
+        Some(generic_params)
+    } else {
+        None
+    };
+
+    let new_type = NameAndGenericsAstNode {
+        span: start_idx..end_idx,
+        name: operator.get_method_name(),
+        generic_params,
+    };
+
+    Ok((new_type, operator))
+}
+
 pub fn parse_method_args<'a>(
     lxr: &mut LexerStruct<'a>,
-) -> Result<Vec<AstMethodArgument<'a>>, ParseError<'a>> {
+) -> Result<Vec<Documented<'a, AstMethodArgument<'a>>>, ParseError<'a>> {
     flush_comments(lxr);
 
     expect_token(
@@ -162,10 +296,14 @@ pub fn parse_method_args<'a>(
     let mut args = Vec::new();
 
     loop {
-        match lxr.next() {
+        let doc_comments = parse_doc_comments(lxr);
+
+        let arg = match lxr.next() {
             Some(LexerToken::Identifier(arg_name)) => {
                 let start_idx = lxr.span().unwrap().start;
 
+                flush_comments(lxr);
+
                 expect_token(
                     lxr,
                     LexerToken::ThinArrow,
@@ -174,21 +312,17 @@ pub fn parse_method_args<'a>(
 
                 let arg_type = parse_type(lxr, None)?;
 
-                args.push(AstMethodArgument::Regular(
-                    start_idx..lxr.span().unwrap().end,
-                    arg_name,
-                    arg_type,
-                ));
-            },
-            Some(LexerToken::LittleThis) => {
-                args.push(AstMethodArgument::This(lxr.span().unwrap()));
+                AstMethodArgument::Regular(start_idx..lxr.span().unwrap().end, arg_name, arg_type)
             },
+            Some(LexerToken::LittleThis) => AstMethodArgument::This(lxr.span().unwrap()),
             Some(LexerToken::Mutable) => {
                 let start_idx = lxr.span().unwrap().start;
 
+                flush_comments(lxr);
+
                 expect_token(lxr, LexerToken::LittleThis, &["`this`"])?;
 
-                args.push(AstMethodArgument::ThisMut(start_idx..lxr.span().unwrap().end));
+                AstMethodArgument::ThisMut(start_idx..lxr.span().unwrap().end)
             }
             Some(LexerToken::RightParenthesis) => break,
             invalid_value => return Err(call_error(
@@ -203,6 +337,11 @@ pub fn parse_method_args<'a>(
             )),
         };
 
+        let arg_span = arg.get_span();
+        args.push(doc_comments.attach(arg, arg_span));
+
+        flush_comments(lxr);
+
         match lxr.next() {
             Some(LexerToken::Comma) => (),
             Some(LexerToken::RightParenthesis) => break,
@@ -222,3 +361,43 @@ pub fn parse_method_args<'a>(
 
     Ok(args)
 }
+
+/// Fails on the first `this` or `mut this` in `args`, for things that don't have
+/// an instance to take (or that always take one without it being written out).
+pub fn reject_this_args<'a>(
+    lxr: &LexerStruct<'a>,
+    args: &[Documented<'a, AstMethodArgument<'a>>],
+    expected_arr: &'static [&'static str],
+) -> Result<(), ParseError<'a>> {
+    match args.iter().find(|arg| {
+        matches!(
+            arg.node,
+            AstMethodArgument::This(_) | AstMethodArgument::ThisMut(_)
+        )
+    }) {
+        Some(this_arg) => {
+            let this_span = this_arg.node.get_span();
+            Err(ParseError::unexpected_token_error(
+                &lxr.source()[this_span.clone()],
+                this_span,
+                expected_arr,
+                true,
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Parses the optional `-> Type` after the arguments of a method or function.
+pub fn parse_return_type<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<Option<AstType<'a>>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    if let Some(LexerToken::ThinArrow) = lxr.peek() {
+        lxr.next();
+        Ok(Some(parse_type(lxr, None)?))
+    } else {
+        Ok(None)
+    }
+}
@@ -1,10 +1,15 @@
+use logos::Span;
+
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::ast::patterns::AstDestructuringPattern;
+use super::ast::expressions::AstLiteral;
+use super::ast::patterns::{AstBindingPattern, AstDestructuringPattern, AstPattern};
+use super::expressions::get_literal;
 use super::parse_error::ParseError;
 use super::utility_things::{call_error, expect_token, flush_comments, LexerStruct};
 
 type AstDestructuringPatternResult<'a> = Result<AstDestructuringPattern<'a>, ParseError<'a>>;
+type AstPatternResult<'a> = Result<AstPattern<'a>, ParseError<'a>>;
 
 pub fn parse_destructuring_pattern<'a>(
     lxr: &mut LexerStruct<'a>,
@@ -39,44 +44,7 @@ pub fn parse_destructuring_pattern<'a>(
 
             // If this matches, it has parsed... `[name]: {`
             Some(LexerToken::LeftCurlyBrace) => {
-                let mut child_destructuring_patterns = Vec::new();
-                loop {
-                    flush_comments(lxr);
-
-                    match lxr.peek() {
-                        Some(LexerToken::Identifier(_)) => {
-                            child_destructuring_patterns.push(parse_destructuring_pattern(lxr)?)
-                        }
-                        Some(LexerToken::RightCurlyBrace) => {
-                            lxr.next();
-                            break;
-                        }
-                        invalid_value => {
-                            lxr.next();
-                            return Err(call_error(
-                                lxr,
-                                invalid_value,
-                                &["identifier or `}` (as part of a destructuring pattern)"],
-                                true,
-                            ));
-                        }
-                    }
-
-                    flush_comments(lxr);
-
-                    match lxr.next() {
-                        Some(LexerToken::Comma) => (),
-                        Some(LexerToken::RightCurlyBrace) => break,
-                        invalid_value => {
-                            return Err(call_error(
-                                lxr,
-                                invalid_value,
-                                &["`,` or `}` (as part of a destructuring pattern)"],
-                                true,
-                            ))
-                        }
-                    }
-                }
+                let child_destructuring_patterns = parse_destructuring_pattern_list(lxr)?;
 
                 Ok(AstDestructuringPattern::Destructured(
                     base_span.start..lxr.span().unwrap().end,
@@ -105,6 +73,362 @@ pub fn parse_destructuring_pattern<'a>(
     }
     // Otherwise, this matches
     else {
-        Ok(AstDestructuringPattern::Name(base_span, &name))
+        Ok(AstDestructuringPattern::Name(base_span, name))
+    }
+}
+
+/// Parses the comma-separated destructuring patterns inside of curly braces, up
+/// to and including the `}`, assuming that the `{` has already been consumed.
+pub fn parse_destructuring_pattern_list<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<Vec<AstDestructuringPattern<'a>>, ParseError<'a>> {
+    let mut destructuring_patterns = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        match lxr.peek() {
+            Some(LexerToken::Identifier(_)) => {
+                destructuring_patterns.push(parse_destructuring_pattern(lxr)?)
+            }
+            Some(LexerToken::RightCurlyBrace) => {
+                lxr.next();
+                break;
+            }
+            invalid_value => {
+                lxr.next();
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &["identifier or `}` (as part of a destructuring pattern)"],
+                    true,
+                ));
+            }
+        }
+
+        flush_comments(lxr);
+
+        match lxr.next() {
+            Some(LexerToken::Comma) => (),
+            Some(LexerToken::RightCurlyBrace) => break,
+            invalid_value => {
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &["`,` or `}` (as part of a destructuring pattern)"],
+                    true,
+                ))
+            }
+        }
     }
+
+    Ok(destructuring_patterns)
+}
+
+pub fn parse_binding_pattern<'a>(
+    lxr: &mut LexerStruct<'a>,
+    allow_mutable: bool,
+) -> Result<AstBindingPattern<'a>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    match lxr.next() {
+        Some(LexerToken::Identifier("_")) => Ok(AstBindingPattern::Ignored(lxr.span().unwrap())),
+        Some(LexerToken::Identifier(name)) => {
+            Ok(AstBindingPattern::Name(lxr.span().unwrap(), name))
+        }
+
+        Some(LexerToken::Mutable) if allow_mutable => {
+            let start_idx = lxr.span().unwrap().start;
+
+            flush_comments(lxr);
+
+            expect_token(
+                lxr,
+                LexerToken::Identifier(""),
+                &["identifier (to name the mutable variable)"],
+            )?;
+
+            Ok(AstBindingPattern::MutableName(
+                start_idx..lxr.span().unwrap().end,
+                lxr.slice().unwrap(),
+            ))
+        }
+
+        Some(LexerToken::LeftParenthesis) => {
+            let start_idx = lxr.span().unwrap().start;
+
+            let mut elements = Vec::new();
+            loop {
+                flush_comments(lxr);
+
+                if let Some(LexerToken::RightParenthesis) = lxr.peek() {
+                    lxr.next();
+                    break;
+                }
+
+                elements.push(parse_binding_pattern(lxr, allow_mutable)?);
+
+                flush_comments(lxr);
+
+                match lxr.next() {
+                    Some(LexerToken::Comma) => (),
+                    Some(LexerToken::RightParenthesis) => break,
+                    invalid_value => {
+                        return Err(call_error(
+                            lxr,
+                            invalid_value,
+                            &["`,` or `)` (as part of a tuple pattern)"],
+                            true,
+                        ))
+                    }
+                }
+            }
+
+            Ok(AstBindingPattern::Tuple(
+                start_idx..lxr.span().unwrap().end,
+                elements,
+            ))
+        }
+
+        invalid_value => Err(call_error(
+            lxr,
+            invalid_value,
+            if allow_mutable {
+                &["identifier, `mut`, or `(` (as part of a binding pattern)"]
+            } else {
+                &["identifier or `(` (as part of a binding pattern)"]
+            },
+            true,
+        )),
+    }
+}
+
+/// Parses a refutable pattern (as used by the arms of a `match`), including
+/// or-patterns like `Rock | Papr`.
+pub fn parse_pattern<'a>(lxr: &mut LexerStruct<'a>) -> AstPatternResult<'a> {
+    let first_pattern = parse_single_pattern(lxr)?;
+
+    flush_comments(lxr);
+
+    if let Some(LexerToken::VerticalBar) = lxr.peek() {
+        let mut alternatives = vec![first_pattern];
+
+        while let Some(LexerToken::VerticalBar) = lxr.peek() {
+            lxr.next();
+            alternatives.push(parse_single_pattern(lxr)?);
+            flush_comments(lxr);
+        }
+
+        Ok(AstPattern::Or(
+            alternatives[0].get_span().start..lxr.span().unwrap().end,
+            alternatives,
+        ))
+    } else {
+        Ok(first_pattern)
+    }
+}
+
+fn parse_single_pattern<'a>(lxr: &mut LexerStruct<'a>) -> AstPatternResult<'a> {
+    flush_comments(lxr);
+
+    match lxr.peek() {
+        Some(LexerToken::Identifier("_")) => {
+            lxr.next();
+            Ok(AstPattern::Wildcard(lxr.span().unwrap()))
+        }
+        Some(LexerToken::Identifier(_)) => parse_path_or_case_pattern(lxr),
+
+        Some(LexerToken::Mutable) => {
+            lxr.next();
+            let start_idx = lxr.span().unwrap().start;
+
+            flush_comments(lxr);
+
+            expect_token(
+                lxr,
+                LexerToken::Identifier(""),
+                &["identifier (to name the mutable binding)"],
+            )?;
+
+            Ok(AstPattern::MutableName(
+                start_idx..lxr.span().unwrap().end,
+                lxr.slice().unwrap(),
+            ))
+        }
+
+        Some(LexerToken::LeftParenthesis) => {
+            lxr.next();
+            let start_idx = lxr.span().unwrap().start;
+
+            flush_comments(lxr);
+
+            if let Some(LexerToken::RightParenthesis) = lxr.peek() {
+                lxr.next();
+                return Ok(AstPattern::Tuple(
+                    start_idx..lxr.span().unwrap().end,
+                    Vec::new(),
+                ));
+            }
+
+            let first_pattern = parse_pattern(lxr)?;
+
+            flush_comments(lxr);
+
+            match lxr.next() {
+                // A single parenthesized pattern is just that pattern, not a tuple.
+                Some(LexerToken::RightParenthesis) => Ok(first_pattern),
+                Some(LexerToken::Comma) => {
+                    let mut elements = vec![first_pattern];
+                    elements.append(&mut parse_pattern_list(lxr)?);
+
+                    Ok(AstPattern::Tuple(
+                        start_idx..lxr.span().unwrap().end,
+                        elements,
+                    ))
+                }
+                invalid_value => Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &["`,` or `)` (as part of a pattern)"],
+                    true,
+                )),
+            }
+        }
+
+        _ => parse_literal_or_range_pattern(lxr),
+    }
+}
+
+/// Parses a name (`hand`), a path (`RoundResult.CWin`), or an enum case with a
+/// payload (`Some(hand)`).
+fn parse_path_or_case_pattern<'a>(lxr: &mut LexerStruct<'a>) -> AstPatternResult<'a> {
+    expect_token(
+        lxr,
+        LexerToken::Identifier(""),
+        &["identifier (as part of a pattern)"],
+    )?;
+    let start_idx = lxr.span().unwrap().start;
+
+    let mut path = vec![lxr.slice().unwrap()];
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::MemberAccess) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
+        }
+
+        flush_comments(lxr);
+
+        expect_token(
+            lxr,
+            LexerToken::Identifier(""),
+            &["identifier (as part of a path)"],
+        )?;
+        path.push(lxr.slice().unwrap());
+    }
+
+    if let Some(LexerToken::LeftParenthesis) = lxr.peek() {
+        lxr.next();
+
+        let payload = parse_pattern_list(lxr)?;
+
+        Ok(AstPattern::Case(
+            start_idx..lxr.span().unwrap().end,
+            path,
+            payload,
+        ))
+    } else if path.len() == 1 {
+        Ok(AstPattern::Name(lxr.span().unwrap(), path[0]))
+    } else {
+        Ok(AstPattern::Path(start_idx..lxr.span().unwrap().end, path))
+    }
+}
+
+fn parse_literal_or_range_pattern<'a>(lxr: &mut LexerStruct<'a>) -> AstPatternResult<'a> {
+    let start_literal = parse_literal_in_pattern(lxr)?;
+    let start_idx = start_literal.0.start;
+
+    flush_comments(lxr);
+
+    if let Some(LexerToken::DoubleDot) = lxr.peek() {
+        lxr.next();
+
+        let end_literal = parse_literal_in_pattern(lxr)?;
+
+        Ok(AstPattern::Range(
+            start_idx..end_literal.0.end,
+            start_literal.1,
+            end_literal.1,
+        ))
+    } else {
+        Ok(AstPattern::Literal(start_literal.0, start_literal.1))
+    }
+}
+
+/// Parses a literal, allowing a leading `-` on numbers.
+fn parse_literal_in_pattern<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<(Span, AstLiteral), ParseError<'a>> {
+    const EXPECTED_ARR: &[&str] = &["pattern (an identifier, literal, `_`, `mut`, or `(`)"];
+
+    flush_comments(lxr);
+
+    let token = lxr.next();
+    let start_idx = lxr.span().unwrap_or(usize::MAX..usize::MAX).start;
+
+    let is_negative = matches!(token, Some(LexerToken::Dash));
+    let token = if is_negative {
+        flush_comments(lxr);
+        lxr.next()
+    } else {
+        token
+    };
+
+    let literal = match (token.as_ref().and_then(get_literal), is_negative) {
+        (Some(literal), false) => literal,
+        (Some(AstLiteral::Integer(value)), true) => AstLiteral::Integer(-value),
+        (Some(AstLiteral::Float(value)), true) => AstLiteral::Float(-value),
+        _ => return Err(call_error(lxr, token, EXPECTED_ARR, true)),
+    };
+
+    Ok((start_idx..lxr.span().unwrap().end, literal))
+}
+
+/// Parses a comma-seperated list of patterns (allowing a trailing comma), up to
+/// and including the closing `)`.
+fn parse_pattern_list<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<Vec<AstPattern<'a>>, ParseError<'a>> {
+    let mut patterns = Vec::new();
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::RightParenthesis) = lxr.peek() {
+            lxr.next();
+            break;
+        }
+
+        patterns.push(parse_pattern(lxr)?);
+
+        flush_comments(lxr);
+
+        match lxr.next() {
+            Some(LexerToken::Comma) => (),
+            Some(LexerToken::RightParenthesis) => break,
+            invalid_value => {
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &["`,` or `)` (as part of a pattern)"],
+                    true,
+                ))
+            }
+        }
+    }
+
+    Ok(patterns)
 }
@@ -1,9 +1,15 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::ast::publicity::{AstClassItemPublicity, AstPublicity, InterfaceMethodPublicity};
+use super::ast::publicity::{
+    AstClassItemPublicity, AstPublicity, EnumCaseFieldPublicity, InterfaceMethodPublicity,
+};
 use super::parse_error::ParseError;
-use super::utility_things::{call_error, LexerStruct};
+use super::utility_things::{call_peeked_error, LexerStruct};
 
+/// Implemented by every kind of publicity that a member of a type body can have.
+///
+/// On success, `parse_publicity` consumes the publicity keyword (if there is
+/// one). On failure, nothing is consumed.
 pub trait ParsePublicity {
     fn parse_publicity<'a>(lxr: &mut LexerStruct<'a>) -> Result<Self, ParseError<'a>>
     where
@@ -12,40 +18,81 @@ pub trait ParsePublicity {
 
 impl ParsePublicity for AstPublicity {
     fn parse_publicity<'a>(lxr: &mut LexerStruct<'a>) -> Result<Self, ParseError<'a>> {
-        match lxr.peek() {
-            Some(LexerToken::Public) => Ok(AstPublicity::Public),
-            Some(LexerToken::ModulePrivate) => Ok(AstPublicity::ModulePrivate),
-            Some(LexerToken::Private) => Ok(AstPublicity::Private),
-            invalid_token => Err(call_error(
+        let publicity = match lxr.peek() {
+            Some(LexerToken::Public) => AstPublicity::Public,
+            Some(LexerToken::ModulePrivate) => AstPublicity::ModulePrivate,
+            Some(LexerToken::Private) => AstPublicity::Private,
+            invalid_token => {
+                return Err(call_peeked_error(
+                    lxr,
+                    invalid_token,
+                    &["[pub, mpriv, or priv] (to declare the publicity of a struct item)"],
+                    false,
+                ))
+            }
+        };
+        lxr.next();
+
+        Ok(publicity)
+    }
+}
+
+impl ParsePublicity for AstClassItemPublicity {
+    fn parse_publicity<'a>(lxr: &mut LexerStruct<'a>) -> Result<Self, ParseError<'a>> {
+        let publicity = match lxr.peek() {
+            Some(LexerToken::Public) => AstClassItemPublicity::Public,
+            Some(LexerToken::ModuleProtected) => AstClassItemPublicity::ModuleProtected,
+            Some(LexerToken::Protected) => AstClassItemPublicity::Protected,
+            Some(LexerToken::ModulePrivate) => AstClassItemPublicity::ModulePrivate,
+            Some(LexerToken::Private) => AstClassItemPublicity::Private,
+            invalid_token => return Err(call_peeked_error(
                 lxr,
                 invalid_token,
-                &["[pub, mpriv, or priv] (to declare the publicity of a struct item)"],
+                &["[pub, mprot, prot, mpriv, or priv] (to declare the publicity of a class item)"],
                 false,
             )),
-        }
+        };
+        lxr.next();
+
+        Ok(publicity)
     }
 }
 
-impl ParsePublicity for AstClassItemPublicity {
+/// Interface methods are always public, so the `pub` is optional.
+impl ParsePublicity for InterfaceMethodPublicity {
     fn parse_publicity<'a>(lxr: &mut LexerStruct<'a>) -> Result<Self, ParseError<'a>> {
         match lxr.peek() {
-            Some(LexerToken::Public) => Ok(AstClassItemPublicity::Public),
-            Some(LexerToken::ModuleProtected) => Ok(AstClassItemPublicity::ModuleProtected),
-            Some(LexerToken::Protected) => Ok(AstClassItemPublicity::Protected),
-            Some(LexerToken::ModulePrivate) => Ok(AstClassItemPublicity::ModulePrivate),
-            Some(LexerToken::Private) => Ok(AstClassItemPublicity::Private),
-            invalid_token => Err(call_error(
+            Some(LexerToken::Public) => {
+                lxr.next();
+                Ok(InterfaceMethodPublicity::Public)
+            }
+            Some(LexerToken::Function) => Ok(InterfaceMethodPublicity::Public),
+            invalid_token => Err(call_peeked_error(
                 lxr,
                 invalid_token,
-                &["[pub, mprot, prot, mpriv, or priv] (to declare the publicity of a class item)"],
+                &["`pub` or `fun` (to declare an interface method)"],
                 false,
             )),
         }
     }
 }
 
-impl ParsePublicity for InterfaceMethodPublicity {
-    fn parse_publicity<'a>(_lxr: &mut LexerStruct<'a>) -> Result<Self, ParseError<'a>> {
-        Ok(InterfaceMethodPublicity::Public)
+/// Fields of enum cases are as public as the enum itself, so the `pub` is
+/// optional.
+impl ParsePublicity for EnumCaseFieldPublicity {
+    fn parse_publicity<'a>(lxr: &mut LexerStruct<'a>) -> Result<Self, ParseError<'a>> {
+        match lxr.peek() {
+            Some(LexerToken::Public) => {
+                lxr.next();
+                Ok(EnumCaseFieldPublicity::Public)
+            }
+            Some(LexerToken::Identifier(_)) => Ok(EnumCaseFieldPublicity::Public),
+            invalid_token => Err(call_peeked_error(
+                lxr,
+                invalid_token,
+                &["`pub` or identifier (to declare a field of the enum case)"],
+                false,
+            )),
+        }
     }
 }
@@ -0,0 +1,136 @@
+use super::super::lexer::logos_lexer::LexerToken;
+
+use super::ast::expressions::{AstExpression, AstStatement, AstVariableKind, VariableDecAstNode};
+use super::expressions::parse_expression;
+use super::parse_error::ParseError;
+use super::patterns::parse_binding_pattern;
+use super::types::parse_type;
+use super::utility_things::{
+    call_error, expect_semicolon, expect_token, flush_comments, LexerStruct,
+};
+
+/// Parses a `let` or `const` declaration, starting from the `let` or `const`
+/// keyword and ending with the semicolon.
+pub fn parse_variable_declaration<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<VariableDecAstNode<'a>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    let kind = match lxr.next() {
+        Some(LexerToken::Let) => AstVariableKind::Let,
+        Some(LexerToken::Const) => AstVariableKind::Const,
+        invalid_value => {
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`let` or `const` (to declare a variable)"],
+                false,
+            ))
+        }
+    };
+
+    parse_variable_declaration_rest(lxr, kind)
+}
+
+/// Parses the rest of a `let` or `const` declaration, assuming that the keyword
+/// (which is given by `kind`) has already been consumed.
+///
+/// `const` declarations are guaranteed to have a value.
+pub fn parse_variable_declaration_rest<'a>(
+    lxr: &mut LexerStruct<'a>,
+    kind: AstVariableKind,
+) -> Result<VariableDecAstNode<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let pattern = parse_binding_pattern(lxr, matches!(kind, AstVariableKind::Let))?;
+
+    flush_comments(lxr);
+
+    let var_type = match lxr.peek() {
+        Some(LexerToken::Colon) => {
+            lxr.next();
+            flush_comments(lxr);
+            Some(parse_type(lxr, None)?)
+        }
+        // `->` is how fields are annotated, but not variables.
+        invalid_value @ Some(LexerToken::ThinArrow) => {
+            lxr.next();
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                match kind {
+                    AstVariableKind::Let => {
+                        &["`:` instead of `->` (to denote the type of the variable)"]
+                    }
+                    AstVariableKind::Const => {
+                        &["`:` instead of `->` (to denote the type of the constant)"]
+                    }
+                },
+                true,
+            ));
+        }
+        _ => None,
+    };
+
+    flush_comments(lxr);
+
+    let value = match lxr.peek() {
+        Some(LexerToken::Assign) => {
+            lxr.next();
+            Some(parse_expression(lxr)?)
+        }
+        invalid_value if matches!(kind, AstVariableKind::Const) => {
+            lxr.next();
+            return Err(call_error(
+                lxr,
+                invalid_value,
+                &["`=` (to give the constant a value)"],
+                true,
+            ));
+        }
+        _ => None,
+    };
+
+    flush_comments(lxr);
+
+    expect_semicolon(lxr)?;
+
+    Ok(VariableDecAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        kind,
+        pattern,
+        var_type,
+        value,
+    })
+}
+
+/// Parses the rest of an assignment statement, assuming that `target` has
+/// already been parsed and that the next token is `=`.
+pub fn parse_assignment<'a>(
+    lxr: &mut LexerStruct<'a>,
+    target: AstExpression<'a>,
+) -> Result<AstStatement<'a>, ParseError<'a>> {
+    if !target.is_assignable() {
+        let target_span = target.get_span();
+        return Err(ParseError::unexpected_token_error(
+            &lxr.source()[target_span.clone()],
+            target_span,
+            &["variable, member, or subscript (to assign to)"],
+            true,
+        ));
+    }
+
+    expect_token(lxr, LexerToken::Assign, &["`=` (to assign a value)"])?;
+
+    let value = parse_expression(lxr)?;
+
+    flush_comments(lxr);
+
+    expect_semicolon(lxr)?;
+
+    Ok(AstStatement::Assignment(
+        target.get_span().start..lxr.span().unwrap().end,
+        target,
+        value,
+    ))
+}
@@ -2,10 +2,10 @@ use super::super::lexer::logos_lexer::LexerToken;
 
 use super::ast::structs::StructDecAstNode;
 use super::extends_implements::parse_implements;
-use super::fields::parse_fields_until_none_are_left;
-use super::methods::parse_methods_until_none_are_found;
+use super::fields::{parse_field, parse_fields_until_none_are_left};
+use super::members::parse_members_until_none_are_found;
 use super::parse_error::{combine_parse_errors, ParseError};
-use super::types::parse_name_and_generics;
+use super::types::{parse_name_and_generics, parse_where_clause};
 use super::utility_things::{expect_token, LexerStruct};
 
 pub fn parse_struct<'a>(lxr: &mut LexerStruct<'a>) -> Result<StructDecAstNode<'a>, ParseError<'a>> {
@@ -15,17 +15,28 @@ pub fn parse_struct<'a>(lxr: &mut LexerStruct<'a>) -> Result<StructDecAstNode<'a
 
     let implements = parse_implements(lxr)?;
 
+    let where_clause = parse_where_clause(lxr)?;
+
     expect_token(
         lxr,
         LexerToken::LeftCurlyBrace,
         &["`{` (to open the body of the struct)"],
     )?;
 
-    let fields_and_final_error = parse_fields_until_none_are_left(lxr)?;
+    let fields_and_final_error = parse_fields_until_none_are_left(lxr, parse_field)?;
     let fields = fields_and_final_error.0;
 
-    let methods_and_final_error = match parse_methods_until_none_are_found(lxr) {
+    let members_and_final_error = match parse_members_until_none_are_found(lxr, false) {
         Ok(thing) => thing,
+        // Whichever of the two errors got further is the only thing that could
+        // have been meant there, so it's the only one reported.
+        Err(err) if err.span.start > fields_and_final_error.1.span.start => return Err(err),
+        Err(err) if err.span.start < fields_and_final_error.1.span.start => {
+            return Err(ParseError {
+                fatal: true,
+                ..fields_and_final_error.1
+            })
+        }
         Err(err) => {
             return Err(ParseError {
                 fatal: true,
@@ -33,21 +44,25 @@ pub fn parse_struct<'a>(lxr: &mut LexerStruct<'a>) -> Result<StructDecAstNode<'a
             })
         }
     };
-    let methods = methods_and_final_error.0;
+    let members = members_and_final_error.0;
 
     if let Err(err) = expect_token(
         lxr,
         LexerToken::RightCurlyBrace,
         &["`}` (to close the body of the struct)"],
     ) {
-        return Err(combine_parse_errors(methods_and_final_error.1, err));
+        return Err(combine_parse_errors(members_and_final_error.1, err));
     }
 
     Ok(StructDecAstNode {
         span: start_span..lxr.span().unwrap().end,
+        attributes: Vec::new(),
+        docs: Vec::new(),
         struct_type,
         implements,
+        where_clause,
         fields,
-        methods,
+        consts: members.consts,
+        methods: members.methods,
     })
 }
@@ -1,12 +1,17 @@
 use super::super::lexer::logos_lexer::LexerToken;
 
-use super::classes::parse_class;
+use super::attributes::{parse_attribute, parse_attributes};
+use super::classes::{parse_class, parse_modified_class};
+use super::constants::parse_const_dec;
 use super::enums::parse_enum_dec;
+use super::functions::parse_function_dec;
 use super::imports_exports::{parse_export_statement, parse_import_statement};
+use super::interfaces::parse_interface;
 use super::structs::parse_struct;
 use super::types::parse_type_alias;
 use super::utility_things::{LexerStruct, TopLevelAstResult};
 
+use super::ast::attributes::AttributeList;
 use super::ast::top_level::TopLevelAstNode;
 use super::parse_error::ParseError;
 
@@ -16,6 +21,9 @@ pub fn parse_top_level<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
         "Enum Declaration",
         "Struct Declaration",
         "Class Declaration",
+        "Interface Declaration",
+        "Function Declaration",
+        "Constant Declaration",
         "Type Alias",
     ];
 
@@ -23,8 +31,12 @@ pub fn parse_top_level<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
         // To deal with stray semicolons. Allows for anything - a class/struct/enum/protocol declaration,
         Some(LexerToken::Semicolon) => parse_top_level(lxr),
 
+        // To deal with ordinary comments, which aren't kept.
+        Some(LexerToken::Comment) => parse_top_level(lxr),
         // To deal with documentation comments and attaching them to the correct top level statement.
-        Some(LexerToken::Comment) => parse_top_level_documentation_comment(lxr),
+        Some(LexerToken::DocComment) => parse_top_level_documentation_comment(lxr),
+        // To deal with attributes and attaching them to the correct declaration.
+        Some(LexerToken::Hash | LexerToken::AtSign) => parse_top_level_attributes(lxr),
         // To deal with parsing export statements.
         Some(LexerToken::Export) => parse_export_statement(lxr),
 
@@ -74,6 +86,42 @@ pub fn parse_top_level<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
             ))
         }
 
+        // To deal with parsing class declarations that start with modifiers (like `abstract`).
+        Some(modifier @ (LexerToken::Abstract | LexerToken::Sealed | LexerToken::Final)) => {
+            let class_declaration_struct = parse_modified_class(lxr, modifier)?;
+            Ok(TopLevelAstNode::ClassDec(
+                class_declaration_struct.span.clone(),
+                class_declaration_struct,
+            ))
+        }
+
+        // To deal with parsing interface declarations. Wraps interface declaration struct in the TopLevelAstNode enum.
+        Some(LexerToken::Interface) => {
+            let interface_declaration_struct = parse_interface(lxr)?;
+            Ok(TopLevelAstNode::InterfaceDec(
+                interface_declaration_struct.span.clone(),
+                interface_declaration_struct,
+            ))
+        }
+
+        // To deal with parsing function declarations. Wraps function declaration struct in the TopLevelAstNode enum.
+        Some(LexerToken::Function) => {
+            let function_declaration_struct = parse_function_dec(lxr)?;
+            Ok(TopLevelAstNode::FunctionDec(
+                function_declaration_struct.span.clone(),
+                function_declaration_struct,
+            ))
+        }
+
+        // To deal with parsing constant declarations. Wraps constant declaration struct in the TopLevelAstNode enum.
+        Some(LexerToken::Const) => {
+            let const_declaration_struct = parse_const_dec(lxr)?;
+            Ok(TopLevelAstNode::ConstDec(
+                const_declaration_struct.span.clone(),
+                const_declaration_struct,
+            ))
+        }
+
         // Error if an disallowed token was found at the top level.
         Some(_) => Err(ParseError::unexpected_token_error(
             lxr.slice().unwrap(),
@@ -87,16 +135,120 @@ pub fn parse_top_level<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
     }
 }
 
+/// Parses the declaration after a documentation comment, assuming that the
+/// comment has already been consumed, and stores the comment on it.
+///
+/// (Documentation comments are stored on the declaration itself, and its span is
+/// extended to cover them.)
 pub fn parse_top_level_documentation_comment<'a>(
     lxr: &mut LexerStruct<'a>,
 ) -> TopLevelAstResult<'a> {
-    let span = lxr.span().unwrap();
-    let slice = lxr.slice().unwrap();
-    parse_top_level(lxr).map(|top_level_statement| {
-        TopLevelAstNode::CommentedNode(
-            span.start..top_level_statement.get_span().end,
-            slice,
-            Box::new(top_level_statement),
-        )
-    })
+    let start_idx = lxr.span().unwrap().start;
+    let doc_comment = lxr.slice().unwrap();
+
+    let mut declaration = parse_top_level(lxr).map_err(|mut error| {
+        error.fatal = true;
+        error
+    })?;
+
+    // Any later documentation comments have already been stored, so this one
+    // goes in front of them.
+    if attach_to_declaration(&mut declaration, start_idx, |_, docs| {
+        docs.insert(0, doc_comment)
+    }) {
+        Ok(declaration)
+    } else {
+        let declaration_span = declaration.get_span();
+        Err(ParseError::unexpected_token_error(
+            &lxr.source()[declaration_span.clone()],
+            declaration_span,
+            &["declaration (to document)"],
+            true,
+        ))
+    }
+}
+
+/// Parses the attributes in front of a declaration, assuming that the `#` or `@`
+/// of the first one has already been consumed.
+///
+/// The attributes are stored on the declaration itself, and its span is
+/// extended to cover them.
+pub fn parse_top_level_attributes<'a>(lxr: &mut LexerStruct<'a>) -> TopLevelAstResult<'a> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let mut attributes = vec![parse_attribute(lxr)?];
+    attributes.append(&mut parse_attributes(lxr)?);
+
+    let mut declaration = parse_top_level(lxr).map_err(|mut error| {
+        error.fatal = true;
+        error
+    })?;
+
+    if attach_to_declaration(&mut declaration, start_idx, |declaration_attributes, _| {
+        *declaration_attributes = attributes
+    }) {
+        Ok(declaration)
+    } else {
+        let declaration_span = declaration.get_span();
+        Err(ParseError::unexpected_token_error(
+            &lxr.source()[declaration_span.clone()],
+            declaration_span,
+            &["declaration (to attach the attributes to)"],
+            true,
+        ))
+    }
+}
+
+/// Calls `attach` with the attributes and documentation comments of the
+/// declaration in `node`, and extends its span to begin at `start_idx`.
+///
+/// Returns `false` if `node` isn't a declaration (or an export of one) that can
+/// have attributes and documentation comments.
+fn attach_to_declaration<'a>(
+    node: &mut TopLevelAstNode<'a>,
+    start_idx: usize,
+    attach: impl FnOnce(&mut AttributeList<'a>, &mut Vec<&'a str>),
+) -> bool {
+    let (node_span, declaration_span, declaration_attributes, declaration_docs) = match node {
+        TopLevelAstNode::EnumDec(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+        TopLevelAstNode::StructDec(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+        TopLevelAstNode::ClassDec(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+        TopLevelAstNode::InterfaceDec(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+        TopLevelAstNode::FunctionDec(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+        TopLevelAstNode::ConstDec(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+        TopLevelAstNode::TypeAlias(span, dec) => {
+            (span, &mut dec.span, &mut dec.attributes, &mut dec.docs)
+        }
+
+        // Attributes and documentation comments in front of `export` belong to
+        // the declaration after it.
+        TopLevelAstNode::Export(span, exported)
+        | TopLevelAstNode::ExportDefault(span, exported) => {
+            let attached = attach_to_declaration(exported, start_idx, attach);
+            if attached {
+                span.start = start_idx;
+            }
+            return attached;
+        }
+
+        _ => return false,
+    };
+
+    node_span.start = start_idx;
+    declaration_span.start = start_idx;
+    attach(declaration_attributes, declaration_docs);
+
+    true
 }
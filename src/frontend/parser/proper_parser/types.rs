@@ -1,7 +1,9 @@
-use std::borrow::Cow;
-use std::collections::HashMap;
-
-use super::ast::types::{AstType, Generics, TypeAliasAstNode};
+use super::ast::documentation::Documented;
+use super::ast::types::{
+    AstType, GenericArgs, GenericParam, GenericParams, NameAndGenericsAstNode, TypeAliasAstNode,
+    WhereClauseAstNode, WherePredicateAstNode,
+};
+use super::documentation::parse_doc_comments;
 use super::parse_error::ParseError;
 use super::utility_things::{
     call_error, expect_semicolon, expect_token, flush_comments, LexerStruct,
@@ -9,149 +11,425 @@ use super::utility_things::{
 
 use super::super::lexer::logos_lexer::LexerToken;
 
-pub fn parse_generics<'a>(
+/// Parses the generic parameters of a declaration (or a constraint block),
+/// assuming that the `<` has already been consumed.
+///
+/// `allow_defaults` allows parameters to be given defaults (as in `T = String`),
+/// which have to come after every parameter without one.
+pub fn parse_generic_params<'a>(
     lxr: &mut LexerStruct<'a>,
-    allow_constraints: bool,
-) -> Result<Generics<'a>, ParseError<'a>> {
-    let starting_span = lxr.span().unwrap();
+    allow_defaults: bool,
+) -> Result<GenericParams<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
 
-    let mut generics = HashMap::new();
+    let mut params: Vec<Documented<'a, GenericParam<'a>>> = Vec::new();
     loop {
-        flush_comments(lxr);
+        let doc_comments = parse_doc_comments(lxr);
 
-        let generic_name: Cow<'a, str> = if allow_constraints {
-            match lxr.next() {
-                Some(LexerToken::RightAngleBracketOrGreaterThan) => break,
-                Some(LexerToken::Identifier(name)) => name,
-                invalid_value => {
-                    return Err(call_error(
-                        lxr,
-                        invalid_value,
-                        &[
-                            "`>` (to close the generics)",
-                            "identifier (to create a new generic)",
-                        ],
-                        true,
-                    ))
-                }
+        split_closing_angle_bracket(lxr);
+
+        let name = match lxr.next() {
+            Some(LexerToken::RightAngleBracketOrGreaterThan) => break,
+            Some(LexerToken::Identifier(name)) => name,
+            invalid_value => {
+                return Err(call_error(
+                    lxr,
+                    invalid_value,
+                    &[
+                        "`>` (to close the generics)",
+                        "identifier (to create a new generic)",
+                    ],
+                    true,
+                ))
             }
-            .into()
-        } else {
-            generics.len().to_string().into()
         };
+        let name_span = lxr.span().unwrap();
 
-        let span = lxr.span().unwrap();
-        let slice = lxr.slice().unwrap();
-
-        const EXPECTED_ARR_2: &[&str] = &["a unique generic name"];
+        if params.iter().any(|param| param.node.name == name) {
+            return Err(ParseError::unexpected_token_error(
+                name,
+                name_span,
+                &["a unique generic name"],
+                true,
+            ));
+        }
 
         flush_comments(lxr);
 
-        if allow_constraints {
-            match lxr.next() {
-                Some(LexerToken::Comma) => {
-                    if generics
-                        .insert(generic_name, (span.clone(), None))
-                        .is_some()
-                    {
-                        return Err(ParseError::unexpected_token_error(
-                            slice,
-                            span,
-                            EXPECTED_ARR_2,
-                            true,
-                        ));
-                    }
-                    continue;
-                }
-                Some(LexerToken::RightAngleBracketOrGreaterThan) => {
-                    if generics
-                        .insert(generic_name, (span.clone(), None))
-                        .is_some()
-                    {
-                        return Err(ParseError::unexpected_token_error(
-                            slice,
-                            span,
-                            EXPECTED_ARR_2,
-                            true,
-                        ));
-                    }
-                    break;
-                }
-                Some(LexerToken::ThinArrow) => (),
-                invalid_value => {
-                    return Err(call_error(
-                        lxr,
-                        invalid_value,
-                        &[
-                            "`>` (to close the generics)",
-                            "`,` (to signal the next generic)",
-                            "`:` (to define a constraint on the current generic)",
-                        ],
-                        true,
-                    ))
-                }
-            }
-        }
+        let bounds = if let Some(LexerToken::ThinArrow) = lxr.peek() {
+            lxr.next();
+            parse_bounds(lxr)?
+        } else {
+            Vec::new()
+        };
 
         flush_comments(lxr);
 
-        let generic_type = parse_type(lxr, None).map_err(|mut err| {
-            err.fatal = true;
-            err
-        })?;
+        let default = match lxr.peek() {
+            Some(LexerToken::Assign) if allow_defaults => {
+                lxr.next();
+                Some(parse_type(lxr, None)?)
+            }
+            _ => None,
+        };
 
-        let result_of_insert = generics.insert(
-            generic_name,
-            (
-                if allow_constraints {
-                    span.start..generic_type.get_span().end
-                } else {
-                    generic_type.get_span()
-                },
-                Some(generic_type),
-            ),
-        );
+        let end_idx = default
+            .as_ref()
+            .or_else(|| bounds.last())
+            .map_or(name_span.end, |last_type| last_type.get_span().end);
+        let span = name_span.start..end_idx;
 
-        if result_of_insert.is_some() {
+        if default.is_none() && params.iter().any(|param| param.node.default.is_some()) {
             return Err(ParseError::unexpected_token_error(
-                slice,
+                &lxr.source()[span.clone()],
                 span,
-                EXPECTED_ARR_2,
+                &["`=` (to give the generic a default, since a generic before it has one)"],
                 true,
             ));
         }
 
+        let expected_arr: &'static [&'static str] = match (bounds.is_empty(), &default) {
+            (_, Some(_)) => &[
+                "`>` (to close the generics)",
+                "`,` (to signal the next generic)",
+            ],
+            (false, None) => &[
+                "`>` (to close the generics)",
+                "`,` (to signal the next generic)",
+                "`&` (to add another bound to the current generic)",
+            ],
+            (true, None) => &[
+                "`>` (to close the generics)",
+                "`,` (to signal the next generic)",
+                "`->` (to bound the current generic)",
+            ],
+        };
+
+        params.push(doc_comments.attach(
+            GenericParam {
+                span: span.clone(),
+                name,
+                bounds,
+                default,
+            },
+            span,
+        ));
+
         flush_comments(lxr);
+        split_closing_angle_bracket(lxr);
 
         match lxr.next() {
             Some(LexerToken::Comma) => (),
             Some(LexerToken::RightAngleBracketOrGreaterThan) => break,
-            invalid_value => {
-                return Err(call_error(
-                    lxr,
-                    invalid_value,
-                    &[
-                        "`>` (to close the generics)",
-                        "`,` (to signal the next generic)",
-                    ],
-                    true,
-                ))
-            }
+            invalid_value => return Err(call_error(lxr, invalid_value, expected_arr, true)),
+        }
+    }
+
+    Ok(GenericParams {
+        span: start_idx..lxr.span().unwrap().end,
+        params,
+    })
+}
+
+/// Parses one or more bounds seperated by `&` (as in `Eq & Hash`), assuming that
+/// the `->` before them has already been consumed.
+fn parse_bounds<'a>(lxr: &mut LexerStruct<'a>) -> Result<Vec<AstType<'a>>, ParseError<'a>> {
+    let mut bounds = vec![parse_type(lxr, None)?];
+
+    loop {
+        flush_comments(lxr);
+
+        if let Some(LexerToken::Ampersand) = lxr.peek() {
+            lxr.next();
+            bounds.push(parse_type(lxr, None)?);
+        } else {
+            break;
+        }
+    }
+
+    Ok(bounds)
+}
+
+/// Parses a `where` clause if there is one. The clause ends at the first
+/// predicate that isn't followed by a `,`.
+pub fn parse_where_clause<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<Option<WhereClauseAstNode<'a>>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    if let Some(LexerToken::Where) = lxr.peek() {
+        lxr.next();
+    } else {
+        return Ok(None);
+    }
+
+    let start_idx = lxr.span().unwrap().start;
+
+    let mut predicates = Vec::new();
+    loop {
+        let bounded_type = parse_type(lxr, None)?;
+
+        flush_comments(lxr);
+
+        expect_token(
+            lxr,
+            LexerToken::ThinArrow,
+            &["`->` (to bound the type in the `where` clause)"],
+        )?;
+
+        let bounds = parse_bounds(lxr)?;
+
+        predicates.push(WherePredicateAstNode {
+            span: bounded_type.get_span().start..lxr.span().unwrap().end,
+            bounded_type,
+            bounds,
+        });
+
+        flush_comments(lxr);
+
+        if let Some(LexerToken::Comma) = lxr.peek() {
+            lxr.next();
+        } else {
+            break;
         }
     }
 
-    Ok((starting_span.start..lxr.span().unwrap().end, generics))
+    Ok(Some(WhereClauseAstNode {
+        span: start_idx..lxr.span().unwrap().end,
+        predicates,
+    }))
+}
+
+/// Parses the generic arguments given to a type, assuming that the `<` has
+/// already been consumed.
+pub fn parse_generic_args<'a>(
+    lxr: &mut LexerStruct<'a>,
+) -> Result<GenericArgs<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    let args = parse_type_list(
+        lxr,
+        LexerToken::RightAngleBracketOrGreaterThan,
+        &[
+            "`>` (to close the generics)",
+            "`,` (to signal the next generic)",
+        ],
+    )?;
+
+    Ok(GenericArgs {
+        span: start_idx..lxr.span().unwrap().end,
+        args,
+    })
 }
 
+/// Parses a type, including tuple types (`(A, B)`), function types
+/// (`fun(A, B) -> C`), and any number of array (`T[]`) and optional (`T?`)
+/// suffixes.
+///
+/// Suffixes bind to the closest type, so `fun() -> A?` returns an optional, while
+/// `(fun() -> A)?` is an optional function.
 pub fn parse_type<'a>(
     lxr: &mut LexerStruct<'a>,
     curr_type: Option<AstType<'a>>,
 ) -> Result<AstType<'a>, ParseError<'a>> {
+    flush_comments(lxr);
+
+    let start_idx = match &curr_type {
+        Some(starting_type) => starting_type.get_span().start,
+        None => lxr.peek_span().unwrap_or(usize::MAX..usize::MAX).start,
+    };
+
+    let mut curr_type = match (curr_type, lxr.peek()) {
+        (None, Some(LexerToken::LeftParenthesis)) => {
+            lxr.next();
+            parse_tuple_type(lxr)?
+        }
+        (None, Some(LexerToken::Function)) => {
+            lxr.next();
+            parse_function_type(lxr)?
+        }
+        (curr_type, _) => parse_named_type(lxr, curr_type)?,
+    };
+
+    loop {
+        flush_comments(lxr);
+
+        match lxr.peek() {
+            Some(LexerToken::Optional) => {
+                lxr.next();
+                curr_type =
+                    AstType::Optional(start_idx..lxr.span().unwrap().end, Box::new(curr_type));
+            }
+            Some(LexerToken::LeftSquareBracket) => {
+                let saved_position = lxr.save_position();
+                lxr.next();
+
+                flush_comments(lxr);
+
+                // A `[` that isn't followed by `]` doesn't belong to the type (it
+                // could be a subscript after a cast, for example).
+                if let Some(LexerToken::RightSquareBracket) = lxr.peek() {
+                    lxr.next();
+                    curr_type =
+                        AstType::Array(start_idx..lxr.span().unwrap().end, Box::new(curr_type));
+                } else {
+                    lxr.return_to_position(saved_position);
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    Ok(curr_type)
+}
+
+/// Parses a tuple type, assuming that the `(` has already been consumed.
+///
+/// A single type in parentheses without a trailing comma is just that type.
+fn parse_tuple_type<'a>(lxr: &mut LexerStruct<'a>) -> Result<AstType<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    flush_comments(lxr);
+
+    if let Some(LexerToken::RightParenthesis) = lxr.peek() {
+        lxr.next();
+        return Ok(AstType::Tuple(
+            start_idx..lxr.span().unwrap().end,
+            Vec::new(),
+        ));
+    }
+
+    let first_type = parse_type(lxr, None)?;
+
+    flush_comments(lxr);
+
+    match lxr.next() {
+        Some(LexerToken::RightParenthesis) => Ok(first_type),
+        Some(LexerToken::Comma) => {
+            let mut element_types = vec![first_type];
+
+            element_types.append(&mut parse_type_list(
+                lxr,
+                LexerToken::RightParenthesis,
+                &[
+                    "`,` (to signal the next element type)",
+                    "`)` (to close the tuple type)",
+                ],
+            )?);
+
+            Ok(AstType::Tuple(
+                start_idx..lxr.span().unwrap().end,
+                element_types,
+            ))
+        }
+        invalid_value => Err(call_error(
+            lxr,
+            invalid_value,
+            &[
+                "`,` (to create a tuple type)",
+                "`)` (to close the parentheses)",
+            ],
+            true,
+        )),
+    }
+}
+
+/// Parses a function type, assuming that the `fun` keyword has already been
+/// consumed. The return type can be left out, just like in a function
+/// declaration.
+fn parse_function_type<'a>(lxr: &mut LexerStruct<'a>) -> Result<AstType<'a>, ParseError<'a>> {
+    let start_idx = lxr.span().unwrap().start;
+
+    flush_comments(lxr);
+
     expect_token(
         lxr,
-        LexerToken::Identifier(""),
-        &["identifier (as part of a type)"],
+        LexerToken::LeftParenthesis,
+        &["`(` (to open the argument types of the function type)"],
     )?;
+
+    let arg_types = parse_type_list(
+        lxr,
+        LexerToken::RightParenthesis,
+        &[
+            "`,` (to signal the next argument type)",
+            "`)` (to close the argument types)",
+        ],
+    )?;
+
+    flush_comments(lxr);
+
+    let return_type = if let Some(LexerToken::ThinArrow) = lxr.peek() {
+        lxr.next();
+        Some(Box::new(parse_type(lxr, None)?))
+    } else {
+        None
+    };
+
+    Ok(AstType::Function(
+        start_idx..lxr.span().unwrap().end,
+        arg_types,
+        return_type,
+    ))
+}
+
+/// Parses a comma-seperated list of types (allowing a trailing comma), up to and
+/// including `closing_token`.
+fn parse_type_list<'a>(
+    lxr: &mut LexerStruct<'a>,
+    closing_token: LexerToken<'a>,
+    expected_arr: &'static [&'static str],
+) -> Result<Vec<AstType<'a>>, ParseError<'a>> {
+    let mut types = Vec::new();
+
+    let closes_generics = closing_token == LexerToken::RightAngleBracketOrGreaterThan;
+
+    loop {
+        flush_comments(lxr);
+        if closes_generics {
+            split_closing_angle_bracket(lxr);
+        }
+
+        if lxr.peek().as_ref() == Some(&closing_token) {
+            lxr.next();
+            break;
+        }
+
+        types.push(parse_type(lxr, None)?);
+
+        flush_comments(lxr);
+        if closes_generics {
+            split_closing_angle_bracket(lxr);
+        }
+
+        match lxr.next() {
+            Some(LexerToken::Comma) => (),
+            Some(ref token) if *token == closing_token => break,
+            invalid_value => return Err(call_error(lxr, invalid_value, expected_arr, true)),
+        }
+    }
+
+    Ok(types)
+}
+
+fn parse_named_type<'a>(
+    lxr: &mut LexerStruct<'a>,
+    curr_type: Option<AstType<'a>>,
+) -> Result<AstType<'a>, ParseError<'a>> {
+    // `This` (the type being declared) can only start a path.
+    match (&curr_type, lxr.peek()) {
+        (None, Some(LexerToken::BigThis)) => {
+            lxr.next();
+        }
+        _ => {
+            expect_token(
+                lxr,
+                LexerToken::Identifier(""),
+                &["identifier (as part of a type)"],
+            )?;
+        }
+    }
     let name = lxr.slice().unwrap();
 
     let mut curr_type = match curr_type {
@@ -166,17 +444,17 @@ pub fn parse_type<'a>(
     match lxr.peek() {
         Some(LexerToken::MemberAccess) => {
             lxr.next();
-            match parse_type(lxr, Some(curr_type)) {
+            match parse_named_type(lxr, Some(curr_type)) {
                 Ok(parsed_type) => curr_type = parsed_type,
                 Err(error) => return Err(error),
             }
         }
         Some(LexerToken::LeftAngleBracketOrLessThan) => {
             lxr.next();
-            match parse_generics(lxr, false) {
+            match parse_generic_args(lxr) {
                 Ok(parsed_generic) => {
                     curr_type = AstType::GenericOf(
-                        curr_type.get_span().start..parsed_generic.0.end,
+                        curr_type.get_span().start..parsed_generic.span.end,
                         Box::new(curr_type),
                         parsed_generic,
                     )
@@ -186,7 +464,7 @@ pub fn parse_type<'a>(
             match lxr.peek() {
                 Some(LexerToken::MemberAccess) => {
                     lxr.next();
-                    match parse_type(lxr, Some(curr_type)) {
+                    match parse_named_type(lxr, Some(curr_type)) {
                         Ok(parsed_type) => curr_type = parsed_type,
                         Err(error) => return Err(error),
                     }
@@ -207,9 +485,22 @@ pub fn parse_type<'a>(
     Ok(curr_type)
 }
 
+/// `>>` and `>=` are lexed as single tokens, so they have to be split up when
+/// their first `>` closes a list of generics (as in `Vec<Vec<T>>` or
+/// `let x: Vec<T>= ...`).
+pub fn split_closing_angle_bracket(lxr: &mut LexerStruct) {
+    let rest = match lxr.peek() {
+        Some(LexerToken::DoubleRightAngleBracket) => LexerToken::RightAngleBracketOrGreaterThan,
+        Some(LexerToken::GreaterThanOrEqualTo) => LexerToken::Assign,
+        _ => return,
+    };
+
+    lxr.split_next_token(1, LexerToken::RightAngleBracketOrGreaterThan, rest);
+}
+
 pub fn parse_name_and_generics<'a>(
     lxr: &mut LexerStruct<'a>,
-) -> Result<AstType<'a>, ParseError<'a>> {
+) -> Result<NameAndGenericsAstNode<'a>, ParseError<'a>> {
     flush_comments(lxr);
 
     expect_token(
@@ -219,24 +510,25 @@ pub fn parse_name_and_generics<'a>(
     )?;
     let name = lxr.slice().unwrap();
 
-    let base_span = lxr.span().unwrap();
-    let base_name = AstType::RootName(base_span, name);
+    let start_idx = lxr.span().unwrap().start;
+    let mut end_idx = lxr.span().unwrap().end;
 
     flush_comments(lxr);
 
-    let generic_declarations = if let Some(LexerToken::LeftAngleBracketOrLessThan) = lxr.peek() {
+    let generic_params = if let Some(LexerToken::LeftAngleBracketOrLessThan) = lxr.peek() {
         lxr.next();
-        parse_generics(lxr, true)?
+        let generic_params = parse_generic_params(lxr, true)?;
+        end_idx = generic_params.span.end;
+        Some(generic_params)
     } else {
-        let full_span = lxr.span().unwrap_or(usize::MAX..usize::MAX);
-        (full_span.start..full_span.start, HashMap::new())
+        None
     };
 
-    Ok(AstType::GenericOf(
-        base_name.get_span().start..lxr.span().unwrap().end,
-        Box::new(base_name),
-        generic_declarations,
-    ))
+    Ok(NameAndGenericsAstNode {
+        span: start_idx..end_idx,
+        name,
+        generic_params,
+    })
 }
 
 pub fn parse_type_alias<'a>(
@@ -261,6 +553,8 @@ pub fn parse_type_alias<'a>(
 
     Ok(TypeAliasAstNode {
         span: start_idx..lxr.span().unwrap().end,
+        attributes: Vec::new(),
+        docs: Vec::new(),
         aliased_type,
         orig_type,
     })
@@ -9,8 +9,10 @@ pub type TopLevelAstResult<'a> = Result<TopLevelAstNode<'a>, ParseError<'a>>;
 
 pub type LexerStruct<'a> = CustomLexerStruct<'a, LexerToken<'a>>;
 
+/// Throws away every comment (documentation or not) up to the next token that
+/// isn't one.
 pub fn flush_comments(lxr: &mut LexerStruct) {
-    while let Some(LexerToken::Comment) = lxr.peek() {
+    while let Some(LexerToken::Comment | LexerToken::DocComment) = lxr.peek() {
         lxr.next();
     }
 }
@@ -76,3 +78,22 @@ pub fn call_error<'a>(
         None => ParseError::end_of_file_error(expected_arr, fatality),
     }
 }
+
+/// Like `call_error`, but for a token that has only been peeked at (and not
+/// consumed), so that the error points at that token instead of the one before it.
+pub fn call_peeked_error<'a>(
+    lxr: &mut LexerStruct<'a>,
+    token: Option<LexerToken<'a>>,
+    expected_arr: &'static [&'static str],
+    fatality: bool,
+) -> ParseError<'a> {
+    match token {
+        Some(_) => ParseError::unexpected_token_error(
+            lxr.peek_slice().unwrap(),
+            lxr.peek_span().unwrap(),
+            expected_arr,
+            fatality,
+        ),
+        None => ParseError::end_of_file_error(expected_arr, fatality),
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(debug_assertions)]
-use uckc::frontend::parser::parse_str;
+use uckc::frontend::{analysis::analyze, parser::parse_str};
 
 #[cfg(debug_assertions)]
 use std::io::{self, Read};
@@ -11,7 +11,17 @@ fn main() {
 
         io::stdin().read_to_string(&mut string).unwrap();
 
-        println!("{:#?}", parse_str(&string));
+        let parse_result = parse_str(&string);
+
+        println!("{:#?}", parse_result);
+
+        if let Ok(nodes) = &parse_result {
+            let diagnostics = analyze(nodes);
+
+            if !diagnostics.is_empty() {
+                println!("{:#?}", diagnostics);
+            }
+        }
     }
     #[cfg(not(debug_assertions))]
     {
@@ -0,0 +1,142 @@
+mod common;
+
+use common::analyze_source;
+
+#[test]
+fn struct_constructions_are_checked_against_the_memberwise_constructor() {
+    let source = "struct Point { pub x -> i32, pub y -> i32, pub z -> i32 = 0, }
+        fun main() { let a = Point(1, 2); let b = Point(1); }";
+
+    assert_eq!(
+        analyze_source(source),
+        [(
+            "`Point`'s memberwise constructor takes 2 argument(s) (one for each field without a default), but 1 were given"
+                .to_string(),
+            "Point(1)"
+        )]
+    );
+}
+
+#[test]
+fn class_constructions_are_checked_against_the_declared_constructors() {
+    let source = "class Plain { pub name -> String = \"\" }
+        class Named {
+            pub name -> String,
+            pub constructor(name -> String) { this.name = name; }
+            pub constructor(first -> String, last -> String) { this.name = first; }
+        }
+        fun main() {
+            Plain(); Plain(1);
+            Named(\"a\"); Named(\"a\", \"b\"); Named();
+        }";
+
+    assert_eq!(
+        analyze_source(source),
+        [
+            (
+                "`Plain` doesn't declare any constructors, so it takes no arguments, but 1 were given"
+                    .to_string(),
+                "Plain(1)"
+            ),
+            (
+                "`Named` doesn't have a constructor that takes 0 argument(s)".to_string(),
+                "Named()"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn constructions_skip_names_that_are_shadowed() {
+    let source = "struct Point { pub x -> i32, }
+        fun Wrapper(a -> i32) -> i32 { a }
+        struct Wrapper { pub a -> i32, pub b -> i32, }
+        fun byArg(Point -> i32) { Point(); }
+        fun byLet() { let Point = fun() => 1; Point(); }
+        fun byClosure() { let f = fun(Point) => Point(); }
+        fun byMatch(a -> i32) { match a { Point => Point(), } }
+        fun byFor(a -> i32) { for Point in a { Point(); } }
+        fun byFunction() { Wrapper(1); }
+        fun outOfScope() { { let Point = 1; } Point(); }";
+
+    assert_eq!(
+        analyze_source(source),
+        [(
+            "`Point`'s memberwise constructor takes 1 argument(s) (one for each field without a default), but 0 were given"
+                .to_string(),
+            "Point()"
+        )]
+    );
+}
+
+#[test]
+fn constants_have_to_be_evaluable_at_compile_time() {
+    let source = "const A: i32 = 1 + 2 * 3;
+        const (B, C) = (A, -A as i64);
+        const D = compute();
+        struct S { pub const E: i32 = A ** 2; pub const F: i32 = { 1 }; }
+        class K { pub const G: i32 = if B { 1 } else { 2 }; }
+        enum N { X, pub const H: i32 = S.E[0]; pub const I: i32 = S.make(); }
+        fun main() { const local = compute(); }";
+
+    let message = "the value of a constant has to be built out of literals, other constants, and operators, so that it can be evaluated at compile time";
+
+    assert_eq!(
+        analyze_source(source),
+        [
+            (message.to_string(), "compute()"),
+            (message.to_string(), "{ 1 }"),
+            (message.to_string(), "if B { 1 } else { 2 }"),
+            (message.to_string(), "S.make()"),
+        ]
+    );
+}
+
+#[test]
+fn inherited_fields_have_to_come_from_exactly_one_superclass() {
+    let source = "class A { pub thing: String, pub only_a: i32, }
+        class B { pub thing: i32, }
+        class C extends A { pub deep: bool, }
+        class D extends C, B {
+            pub only_a,
+            pub deep,
+            pub thing,
+            @Inherit(B) pub thing,
+            @Inherit(A) pub only_a,
+            @Inherit(C.thing) pub renamed,
+            @Inherit(Missing.thing) pub other,
+            @Inherit(B.missing) pub other,
+            pub nowhere,
+        }
+        class E extends Elsewhere { pub anything, }";
+
+    assert_eq!(
+        analyze_source(source),
+        [
+            (
+                "`thing` could be inherited from any of `C`, `B`, so it needs an attribute like `@Inherit(C)` to pick one"
+                    .to_string(),
+                "pub thing"
+            ),
+            (
+                "`only_a` can't be inherited from `A`, since `D` doesn't extend a class with that name"
+                    .to_string(),
+                "@Inherit(A)"
+            ),
+            (
+                "`other` can't be inherited from `Missing`, since `D` doesn't extend a class with that name"
+                    .to_string(),
+                "@Inherit(Missing.thing)"
+            ),
+            (
+                "`B` doesn't have a field named `missing` to inherit".to_string(),
+                "@Inherit(B.missing)"
+            ),
+            (
+                "`nowhere` leaves out its type, but none of the classes that `D` extends have a field named `nowhere` to inherit"
+                    .to_string(),
+                "pub nowhere"
+            ),
+        ]
+    );
+}
@@ -0,0 +1,210 @@
+#![allow(dead_code)]
+
+use uckc::frontend::analysis::analyze;
+use uckc::frontend::parser::parse_str;
+use uckc::frontend::parser::proper_parser::ast::expressions::{
+    AstExpression, AstLiteral, AstStatement, ExpressionBlockAstNode,
+};
+use uckc::frontend::parser::proper_parser::ast::methods::AstOverloadableOperator;
+use uckc::frontend::parser::proper_parser::ast::top_level::TopLevelAstNode;
+use uckc::frontend::parser::proper_parser::ast::types::AstType;
+use uckc::frontend::parser::proper_parser::parse_error::ParseError;
+
+/// Parses `source`, panicking with the error if it doesn't parse.
+pub fn parse_ok(source: &str) -> Vec<TopLevelAstNode<'_>> {
+    match parse_str(source) {
+        Ok(nodes) => nodes,
+        Err(err) => panic!("expected {:?} to parse, but got {:?}", source, err),
+    }
+}
+
+/// Parses `source`, panicking if it parses.
+pub fn parse_err(source: &str) -> ParseError<'_> {
+    match parse_str(source) {
+        Ok(nodes) => panic!("expected {:?} not to parse, but got {:?}", source, nodes),
+        Err(err) => err,
+    }
+}
+
+/// Gets the body of the first function declared in `nodes`.
+pub fn first_function_body<'b, 'a>(
+    nodes: &'b [TopLevelAstNode<'a>],
+) -> &'b ExpressionBlockAstNode<'a> {
+    nodes
+        .iter()
+        .find_map(|node| match node {
+            TopLevelAstNode::FunctionDec(_, function) => Some(&function.body),
+            _ => None,
+        })
+        .expect("expected a function declaration")
+}
+
+/// Parses `expression` as the value of a function body and renders it with
+/// `render_expression`.
+pub fn render_parsed_expression(expression: &str) -> String {
+    let source = format!("fun main() {{ {} }}", expression);
+    let nodes = parse_ok(&source);
+    let body = first_function_body(&nodes);
+
+    assert!(
+        body.statements.is_empty(),
+        "expected {:?} to be a single expression",
+        expression
+    );
+    render_expression(
+        body.value
+            .as_deref()
+            .expect("expected the body to have a value"),
+    )
+}
+
+/// Renders the statements of a block, one per line.
+pub fn render_statements(block: &ExpressionBlockAstNode) -> Vec<String> {
+    block
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            AstStatement::Expression(_, expression) => render_expression(expression),
+            AstStatement::VariableDec(_, declaration) => format!(
+                "(let {})",
+                declaration
+                    .value
+                    .as_ref()
+                    .map(render_expression)
+                    .unwrap_or_default()
+            ),
+            AstStatement::Assignment(_, place, value) => {
+                format!(
+                    "(= {} {})",
+                    render_expression(place),
+                    render_expression(value)
+                )
+            }
+        })
+        .collect()
+}
+
+/// Renders an expression as an S-expression, with every operator and its
+/// operands wrapped in parentheses, so that tests can check how it was
+/// grouped. Expressions that tests don't look inside of are abbreviated.
+pub fn render_expression(expression: &AstExpression) -> String {
+    match expression {
+        AstExpression::Literal(_, literal) => match literal {
+            AstLiteral::String(string) => string.clone(),
+            AstLiteral::Char(char) => format!("{:?}", char),
+            AstLiteral::Integer(integer) => integer.to_string(),
+            AstLiteral::Word(word) => word.to_string(),
+            AstLiteral::Float(float) => float.to_string(),
+            AstLiteral::Bool(bool) => bool.to_string(),
+        },
+        AstExpression::Name(_, name) => name.to_string(),
+        AstExpression::This(_) => "this".to_string(),
+        AstExpression::BigThis(_) => "This".to_string(),
+
+        AstExpression::Parenthesized(_, inner) => format!("(paren {})", render_expression(inner)),
+        AstExpression::Tuple(_, items) => format!("(tuple{})", render_list(items)),
+        AstExpression::Array(_, items) => format!("(array{})", render_list(items)),
+
+        AstExpression::Unary(_, operator, operand) => format!(
+            "({} {})",
+            AstOverloadableOperator::Unary(*operator).get_symbol(),
+            render_expression(operand)
+        ),
+        AstExpression::Binary(_, operator, left, right) => format!(
+            "({} {} {})",
+            AstOverloadableOperator::Binary(*operator).get_symbol(),
+            render_expression(left),
+            render_expression(right)
+        ),
+        AstExpression::Cast(_, value, cast_type) => {
+            format!(
+                "(as {} {})",
+                render_expression(value),
+                render_type(cast_type)
+            )
+        }
+
+        AstExpression::Call(_, callee, args) => {
+            format!("(call {}{})", render_expression(callee), render_list(args))
+        }
+        AstExpression::MacroCall(_, name, args) => format!("(macro {}{})", name, render_list(args)),
+        AstExpression::MemberAccess(_, object, member) => {
+            format!("(. {} {})", render_expression(object), member)
+        }
+        AstExpression::Subscript(_, object, index) => {
+            format!(
+                "(index {} {})",
+                render_expression(object),
+                render_expression(index)
+            )
+        }
+
+        AstExpression::Block(..) => "{block}".to_string(),
+        AstExpression::If(..) => "{if}".to_string(),
+        AstExpression::While(..) => "{while}".to_string(),
+        AstExpression::Loop(..) => "{loop}".to_string(),
+        AstExpression::For(..) => "{for}".to_string(),
+        AstExpression::Match(..) => "{match}".to_string(),
+        AstExpression::Closure(..) => "{closure}".to_string(),
+
+        AstExpression::Break(..) => "break".to_string(),
+        AstExpression::Continue(..) => "continue".to_string(),
+        AstExpression::Return(_, value) => match value {
+            Some(value) => format!("(return {})", render_expression(value)),
+            None => "return".to_string(),
+        },
+        AstExpression::Yield(_, value) => match value {
+            Some(value) => format!("(yield {})", render_expression(value)),
+            None => "yield".to_string(),
+        },
+    }
+}
+
+fn render_list(items: &[AstExpression]) -> String {
+    items
+        .iter()
+        .map(|item| format!(" {}", render_expression(item)))
+        .collect()
+}
+
+/// Renders a type the way it would be written.
+pub fn render_type(ast_type: &AstType) -> String {
+    match ast_type {
+        AstType::RootName(_, name) => name.to_string(),
+        AstType::MemberOf(_, parent, name) => format!("{}.{}", render_type(parent), name),
+        AstType::GenericOf(_, generic, args) => format!(
+            "{}<{}>",
+            render_type(generic),
+            args.args
+                .iter()
+                .map(render_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        AstType::Tuple(_, items) => format!(
+            "({})",
+            items.iter().map(render_type).collect::<Vec<_>>().join(", ")
+        ),
+        AstType::Array(_, item) => format!("[{}]", render_type(item)),
+        AstType::Function(_, args, return_type) => format!(
+            "fun({}){}",
+            args.iter().map(render_type).collect::<Vec<_>>().join(", "),
+            return_type
+                .as_ref()
+                .map(|return_type| format!(" -> {}", render_type(return_type)))
+                .unwrap_or_default()
+        ),
+        AstType::Optional(_, inner) => format!("{}?", render_type(inner)),
+    }
+}
+
+/// Parses and analyzes `source`, returning each diagnostic's message along with
+/// the source code that it points at.
+pub fn analyze_source(source: &str) -> Vec<(String, &str)> {
+    let nodes = parse_ok(source);
+
+    analyze(&nodes)
+        .into_iter()
+        .map(|diagnostic| (diagnostic.message, &source[diagnostic.span]))
+        .collect()
+}
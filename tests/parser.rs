@@ -0,0 +1,239 @@
+mod common;
+
+use std::fs;
+
+use common::{
+    first_function_body, parse_ok, render_parsed_expression, render_statements, render_type,
+};
+use uckc::frontend::parser::proper_parser::ast::expressions::AstStatement;
+use uckc::frontend::parser::proper_parser::ast::top_level::TopLevelAstNode;
+
+#[test]
+fn binary_operators_follow_their_precedence_levels() {
+    // Each operator binds tighter than the one to its left.
+    let levels = ["..", "||", "&&", "==", "|", "^", "&", "<<", "+", "*"];
+
+    for (loose_idx, loose) in levels.iter().enumerate() {
+        for tight in &levels[loose_idx + 1..] {
+            assert_eq!(
+                render_parsed_expression(&format!("a {} b {} c", loose, tight)),
+                format!("({} a ({} b c))", loose, tight)
+            );
+            assert_eq!(
+                render_parsed_expression(&format!("a {} b {} c", tight, loose)),
+                format!("({} ({} a b) c)", loose, tight)
+            );
+        }
+    }
+}
+
+#[test]
+fn operators_on_the_same_level_are_left_associative() {
+    assert_eq!(render_parsed_expression("a - b + c"), "(+ (- a b) c)");
+    assert_eq!(
+        render_parsed_expression("a / b * c % d"),
+        "(% (* (/ a b) c) d)"
+    );
+    assert_eq!(render_parsed_expression("a < b >= c"), "(>= (< a b) c)");
+    assert_eq!(render_parsed_expression("a << b >> c"), "(>> (<< a b) c)");
+}
+
+#[test]
+fn power_is_right_associative_and_binds_tighter_than_unary_operators() {
+    assert_eq!(render_parsed_expression("a ** b ** c"), "(** a (** b c))");
+    assert_eq!(render_parsed_expression("-a ** -b"), "(- (** a (- b)))");
+    assert_eq!(render_parsed_expression("a * b ** c"), "(* a (** b c))");
+}
+
+#[test]
+fn casts_bind_tighter_than_binary_operators() {
+    assert_eq!(
+        render_parsed_expression("a + b * c as i32"),
+        "(+ a (* b (as c i32)))"
+    );
+    assert_eq!(
+        render_parsed_expression("-a as i64 as f64"),
+        "(as (as (- a) i64) f64)"
+    );
+    assert_eq!(
+        render_parsed_expression("a as Vec<i32> == b"),
+        "(== (as a Vec<i32>) b)"
+    );
+}
+
+#[test]
+fn postfix_operators_bind_tightest() {
+    assert_eq!(
+        render_parsed_expression("-a.b(c)[d]?"),
+        "(- (? (index (call (. a b) c) d)))"
+    );
+    assert_eq!(render_parsed_expression("!a.0 ** 2"), "(! (** (. a 0) 2))");
+    assert_eq!(
+        render_parsed_expression("println!(\"{}\", a + b)"),
+        "(macro println \"{}\" (+ a b))"
+    );
+}
+
+#[test]
+fn parentheses_override_precedence() {
+    assert_eq!(
+        render_parsed_expression("(a + b) * c"),
+        "(* (paren (+ a b)) c)"
+    );
+    assert_eq!(render_parsed_expression("(a, b + c)"), "(tuple a (+ b c))");
+}
+
+#[test]
+fn statements_that_start_with_a_block_end_at_its_closing_brace() {
+    let nodes = parse_ok("fun main() { if a { b() } (c, d); { e } - f; }");
+
+    assert_eq!(
+        render_statements(first_function_body(&nodes)),
+        ["{if}", "(tuple c d)", "{block}", "(- f)"]
+    );
+}
+
+#[test]
+fn nested_generic_arguments_close_with_a_shift_token() {
+    let nodes =
+        parse_ok("fun main() { let x: Vec<Vec<i32>> = a; let y: Map<K, Vec<Option<V>>> = b; }");
+    let body = first_function_body(&nodes);
+
+    let annotations: Vec<String> = body
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            AstStatement::VariableDec(_, declaration) => {
+                render_type(declaration.var_type.as_ref().unwrap())
+            }
+            _ => panic!("expected a variable declaration"),
+        })
+        .collect();
+
+    assert_eq!(annotations, ["Vec<Vec<i32>>", "Map<K, Vec<Option<V>>>"]);
+}
+
+#[test]
+fn generic_arguments_close_before_an_equals_sign() {
+    let nodes = parse_ok("fun main() { let x: Vec<i32>= y; let z: Vec<Vec<i32>>= w; }");
+    let body = first_function_body(&nodes);
+
+    assert_eq!(render_statements(body), ["(let y)", "(let w)"]);
+}
+
+#[test]
+fn shift_and_comparison_tokens_are_not_split_in_expressions() {
+    assert_eq!(render_parsed_expression("a >> b"), "(>> a b)");
+    assert_eq!(render_parsed_expression("a >= b"), "(>= a b)");
+}
+
+#[test]
+fn every_example_parses() {
+    let mut paths: Vec<_> = fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/examples"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "uck"))
+        .collect();
+    paths.sort();
+
+    assert!(!paths.is_empty());
+
+    for path in paths {
+        let source = fs::read_to_string(&path).unwrap();
+
+        if let Err(err) = uckc::frontend::parser::parse_str(&source) {
+            panic!("{} doesn't parse: {:?}", path.display(), err);
+        }
+    }
+}
+
+#[test]
+fn documentation_comments_are_stored_on_the_declaration() {
+    let source = "/// Adds.\n#[inline]\n/** Twice. */\nexport fun add(a -> i32) -> i32 { a + a }";
+    let nodes = parse_ok(source);
+
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].get_span().start, 0);
+
+    match nodes[0].get_declaration() {
+        TopLevelAstNode::FunctionDec(span, function) => {
+            assert_eq!(function.docs, ["/// Adds.", "/** Twice. */"]);
+            assert_eq!(function.attributes.len(), 1);
+            assert_eq!(span.start, 0);
+        }
+        other => panic!("expected a function declaration, but got {:?}", other),
+    }
+}
+
+#[test]
+fn documentation_comments_must_come_before_a_declaration() {
+    let err = common::parse_err("/// Stray.\nimport a: { b } from c;");
+
+    assert!(err.fatal);
+    assert_eq!(err.expected[..], ["declaration (to document)"]);
+}
+
+#[test]
+fn errors_in_type_bodies_point_at_the_unexpected_token() {
+    for (source, unexpected) in [
+        ("enum E { A = 1, B = 2, C(i32) = 3 }", "="),
+        ("struct S { pub x -> i32, y -> i32 }", "y"),
+        ("struct S { pub x i32 }", "i32"),
+        ("class C { pub x i32 }", "i32"),
+        ("enum E { A, pub const B i32 = 1; }", "i32"),
+        ("class C { pub fun f() {} 5 }", "5"),
+    ] {
+        let err = common::parse_err(source);
+
+        assert_eq!(err.got.as_deref(), Some(unexpected));
+        assert_eq!(&source[err.span], unexpected);
+    }
+}
+
+#[test]
+fn constants_are_annotated_with_a_colon() {
+    parse_ok(
+        "const K: i32 = 1; struct S { pub const MAX: i32 = 10; } fun f() { const x: i32 = 1; }",
+    );
+
+    for (source, expected) in [
+        (
+            "struct S { pub const MAX -> i32 = 10; }",
+            "`:` instead of `->` (to denote the type of the constant)",
+        ),
+        (
+            "class C { pub const MAX -> i32 = 10; }",
+            "`:` instead of `->` (to denote the type of the constant)",
+        ),
+        (
+            "const K -> i32 = 1;",
+            "`:` instead of `->` (to denote the type of the constant)",
+        ),
+        (
+            "fun f() { let x -> i32 = 1; }",
+            "`:` instead of `->` (to denote the type of the variable)",
+        ),
+    ] {
+        let err = common::parse_err(source);
+
+        assert_eq!(err.expected[..], [expected]);
+        assert_eq!(&source[err.span], "->");
+    }
+}
+
+#[test]
+fn field_types_follow_an_arrow_or_a_colon() {
+    parse_ok(
+        "struct S { pub x -> i32, pub y: i32 = 0, } \
+         class C { pub a: String, pub b -> i32, pub static count: u64 = 0; }",
+    );
+
+    let source = "struct S { pub x i32 }";
+    let err = common::parse_err(source);
+
+    assert_eq!(
+        err.expected[..],
+        ["`->` or `:` (to denote the type of the field)"]
+    );
+    assert_eq!(&source[err.span], "i32");
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use common::{first_function_body, parse_err, parse_ok, render_expression};
+use uckc::frontend::parser::proper_parser::ast::expressions::{
+    AstExpression, AstLiteral, MatchAstNode,
+};
+use uckc::frontend::parser::proper_parser::ast::patterns::AstPattern;
+
+/// Parses `match x { <arms> }` as the value of a function body, and passes the
+/// match to `check`.
+fn with_match(arms: &str, check: impl FnOnce(&MatchAstNode)) {
+    let source = format!("fun main() {{ match x {{ {} }} }}", arms);
+    let nodes = parse_ok(&source);
+
+    match first_function_body(&nodes).value.as_deref() {
+        Some(AstExpression::Match(_, match_node)) => check(match_node),
+        other => panic!("expected a match expression, but got {:?}", other),
+    }
+}
+
+/// Renders a pattern as an S-expression.
+fn render_pattern(pattern: &AstPattern) -> String {
+    fn render_literal(literal: &AstLiteral) -> String {
+        match literal {
+            AstLiteral::String(string) => string.clone(),
+            AstLiteral::Char(char) => format!("{:?}", char),
+            AstLiteral::Integer(integer) => integer.to_string(),
+            AstLiteral::Word(word) => word.to_string(),
+            AstLiteral::Float(float) => float.to_string(),
+            AstLiteral::Bool(bool) => bool.to_string(),
+        }
+    }
+
+    fn render_list(patterns: &[AstPattern]) -> String {
+        patterns
+            .iter()
+            .map(|pattern| format!(" {}", render_pattern(pattern)))
+            .collect()
+    }
+
+    match pattern {
+        AstPattern::Wildcard(_) => "_".to_string(),
+        AstPattern::Literal(_, literal) => render_literal(literal),
+        AstPattern::Range(_, start, end) => {
+            format!("(.. {} {})", render_literal(start), render_literal(end))
+        }
+        AstPattern::Name(_, name) => name.to_string(),
+        AstPattern::MutableName(_, name) => format!("(mut {})", name),
+        AstPattern::Path(_, path) => path.join("."),
+        AstPattern::Case(_, path, payload) => {
+            format!("(case {}{})", path.join("."), render_list(payload))
+        }
+        AstPattern::Tuple(_, items) => format!("(tuple{})", render_list(items)),
+        AstPattern::Or(_, alternatives) => format!("(or{})", render_list(alternatives)),
+    }
+}
+
+fn render_arm_patterns(arms: &str) -> Vec<String> {
+    let mut rendered = Vec::new();
+    with_match(arms, |match_node| {
+        rendered = match_node
+            .arms
+            .iter()
+            .map(|arm| render_pattern(&arm.pattern))
+            .collect();
+    });
+    rendered
+}
+
+#[test]
+fn refutable_patterns() {
+    assert_eq!(
+        render_arm_patterns(
+            "_ => a, 1 => b, -2 => c, \"s\" => d, true => e, 1..5 => g, -3..-1 => h, \
+             hand => i, mut count => j, RoundResult.Draw => k, Some(inner) => l, \
+             Shape.Rect(w, _) => m, (1, (y, z)) => n, () => o, (single) => p,"
+        ),
+        [
+            "_",
+            "1",
+            "-2",
+            "\"s\"",
+            "true",
+            "(.. 1 5)",
+            "(.. -3 -1)",
+            "hand",
+            "(mut count)",
+            "RoundResult.Draw",
+            "(case Some inner)",
+            "(case Shape.Rect w _)",
+            "(tuple 1 (tuple y z))",
+            "(tuple)",
+            "single",
+        ]
+    );
+}
+
+#[test]
+fn or_patterns_can_be_nested() {
+    assert_eq!(
+        render_arm_patterns("Rock | Papr => a, Some(1 | 2) | None => b"),
+        ["(or Rock Papr)", "(or (case Some (or 1 2)) None)"]
+    );
+}
+
+#[test]
+fn match_guards() {
+    with_match(
+        "Some(n) if n > 0 && n < 10 => a, Some(_) => b, None if c => d",
+        |match_node| {
+            let guards: Vec<Option<String>> = match_node
+                .arms
+                .iter()
+                .map(|arm| arm.guard.as_ref().map(render_expression))
+                .collect();
+
+            assert_eq!(
+                guards,
+                [
+                    Some("(&& (> n 0) (< n 10))".to_string()),
+                    None,
+                    Some("c".to_string())
+                ]
+            );
+        },
+    );
+}
+
+#[test]
+fn block_like_arm_bodies_need_no_comma() {
+    with_match(
+        "0 => { a } 1 => if b { c } else { d } 2 => match e { _ => f } 3 => g, _ => { h },",
+        |match_node| {
+            let bodies: Vec<String> = match_node
+                .arms
+                .iter()
+                .map(|arm| render_expression(&arm.body))
+                .collect();
+
+            assert_eq!(bodies, ["{block}", "{if}", "{match}", "g", "{block}"]);
+        },
+    );
+}
+
+#[test]
+fn the_last_arm_needs_no_comma() {
+    with_match("0 => a, _ => b", |match_node| {
+        assert_eq!(match_node.arms.len(), 2)
+    });
+    with_match("", |match_node| assert!(match_node.arms.is_empty()));
+}
+
+#[test]
+fn arms_without_an_arrow_are_rejected() {
+    let err = parse_err("fun main() { match x { 0 a, _ => b } }");
+
+    assert!(err.fatal);
+    assert_eq!(err.got.as_deref(), Some("a"));
+    assert_eq!(
+        err.expected[..],
+        ["`=>` (to give the body of the match arm)"]
+    );
+}
+
+#[test]
+fn guards_without_an_arrow_are_rejected() {
+    let err = parse_err("fun main() { match x { n if n > 0 { a } } }");
+
+    assert!(err.fatal);
+}
+
+#[test]
+fn non_block_arms_without_a_comma_are_rejected() {
+    let source = "fun main() { match x { 0 => a _ => b } }";
+    let err = parse_err(source);
+
+    assert!(err.fatal);
+    assert_eq!(err.got.as_deref(), Some("_"));
+    assert_eq!(&source[err.span], "_");
+    assert_eq!(
+        err.expected[..],
+        [
+            "`,` (to signal the next match arm)",
+            "`}` (to close the match arms)"
+        ]
+    );
+}
+
+#[test]
+fn patterns_with_unclosed_payloads_are_rejected() {
+    let err = parse_err("fun main() { match x { Some(a => b } }");
+
+    assert!(err.fatal);
+}